@@ -0,0 +1,383 @@
+//! Differential testing: several implementations of the same part are run on generated inputs,
+//! and any input they disagree on is cut down to as little as still shows the disagreement.
+
+use std::any::Any;
+use std::fmt;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Answer, Generator, Part, Solution, Streaming};
+
+/// One way of solving one part, straight from the raw input.
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub part: Part,
+    pub name: &'static str,
+    pub solve: fn(&str) -> Result<Answer, String>,
+}
+
+impl Implementation {
+    pub const fn new(
+        part: Part,
+        name: &'static str,
+        solve: fn(&str) -> Result<Answer, String>,
+    ) -> Self {
+        Implementation { part, name, solve }
+    }
+
+    /// `part` as the [`Solution`] itself solves it, parsing included.
+    pub const fn solution<S: Solution>(part: Part) -> Self {
+        let solve = match part {
+            Part::One => solution_part1::<S>,
+            Part::Two => solution_part2::<S>,
+        };
+
+        Implementation::new(part, "solution", solve)
+    }
+
+    /// `part` as the [`Streaming`] solver solves it, reading the input as a stream.
+    pub const fn streaming<S>(part: Part) -> Self
+    where
+        S: Streaming,
+        S::ParseError: fmt::Display,
+    {
+        let solve = match part {
+            Part::One => streaming_part1::<S>,
+            Part::Two => streaming_part2::<S>,
+        };
+
+        Implementation::new(part, "streaming", solve)
+    }
+
+    pub fn run(&self, input: &str) -> Outcome {
+        match panic::catch_unwind(AssertUnwindSafe(|| (self.solve)(input))) {
+            Ok(Ok(answer)) => Outcome::Answer(answer),
+            Ok(Err(e)) => Outcome::Error(e),
+            Err(payload) => Outcome::Panic(panic_message(payload)),
+        }
+    }
+}
+
+fn solution_part1<S: Solution>(input: &str) -> Result<Answer, String> {
+    let parsed = S::parse(input).map_err(|e| e.to_string())?;
+
+    Ok(S::part1(&parsed).into())
+}
+
+fn solution_part2<S: Solution>(input: &str) -> Result<Answer, String> {
+    let parsed = S::parse(input).map_err(|e| e.to_string())?;
+
+    Ok(S::part2(&parsed).into())
+}
+
+fn streaming_part1<S>(input: &str) -> Result<Answer, String>
+where
+    S: Streaming,
+    S::ParseError: fmt::Display,
+{
+    let (answer1, _) = S::solve_stream(input.as_bytes()).map_err(|e| e.to_string())?;
+
+    Ok(answer1.into())
+}
+
+fn streaming_part2<S>(input: &str) -> Result<Answer, String>
+where
+    S: Streaming,
+    S::ParseError: fmt::Display,
+{
+    let (_, answer2) = S::solve_stream(input.as_bytes()).map_err(|e| e.to_string())?;
+
+    Ok(answer2.into())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => String::from(*message),
+            Err(_) => String::from("(no message)"),
+        },
+    }
+}
+
+/// What an implementation made of an input.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Answer(Answer),
+    Error(String),
+    Panic(String),
+}
+
+impl Outcome {
+    /// Whether two outcomes agree. Errors and panics only need to be of the same kind, since
+    /// different implementations are free to word them differently.
+    pub fn agrees_with(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Answer(a), Outcome::Answer(b)) => a == b,
+            (Outcome::Error(_), Outcome::Error(_)) => true,
+            (Outcome::Panic(_), Outcome::Panic(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{}", answer),
+            Outcome::Error(e) => write!(f, "error: {}", e),
+            Outcome::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// The first generated input the implementations of a part disagreed on.
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub part: Part,
+    pub seed: u64,
+    /// The generated input, minimized when the implementations disagree among themselves. When
+    /// they only disagree with the generator, it is left as generated.
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "implementations of part {} disagree on the input from seed {}:",
+            self.part, self.seed
+        )?;
+        for (name, outcome) in &self.outcomes {
+            writeln!(f, "    {}: {}", name, outcome)?;
+        }
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+/// Runs every implementation on inputs generated from each of `seeds`, checking that they agree
+/// with each other and with the answers the inputs were generated to have.
+pub fn cross_check(
+    generator: &dyn Generator,
+    implementations: &[Implementation],
+    seeds: Range<u64>,
+    size: usize,
+) -> Result<(), Divergence> {
+    for seed in seeds {
+        let generated = generator.generate(seed, size);
+
+        for (part, expected) in [
+            (Part::One, generated.answer1),
+            (Part::Two, generated.answer2),
+        ] {
+            let implementations = implementations
+                .iter()
+                .filter(|implementation| implementation.part == part)
+                .collect::<Vec<_>>();
+            let expected = Outcome::Answer(expected);
+            let outcomes = run_all(&implementations, &generated.input);
+
+            if outcomes
+                .iter()
+                .all(|(_, outcome)| outcome.agrees_with(&expected))
+            {
+                continue;
+            }
+
+            let divergence = if disagree(&outcomes) {
+                let input = minimize(&generated.input, |input| {
+                    disagree(&run_all(&implementations, input))
+                });
+
+                Divergence {
+                    part,
+                    seed,
+                    outcomes: run_all(&implementations, &input),
+                    input,
+                }
+            } else {
+                let mut outcomes = outcomes;
+                outcomes.insert(0, ("generated", expected));
+
+                Divergence {
+                    part,
+                    seed,
+                    input: generated.input,
+                    outcomes,
+                }
+            };
+
+            return Err(divergence);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_all(implementations: &[&Implementation], input: &str) -> Vec<(&'static str, Outcome)> {
+    implementations
+        .iter()
+        .map(|implementation| (implementation.name, implementation.run(input)))
+        .collect()
+}
+
+fn disagree(outcomes: &[(&str, Outcome)]) -> bool {
+    outcomes
+        .windows(2)
+        .any(|pair| !pair[0].1.agrees_with(&pair[1].1))
+}
+
+/// Cuts `input` down for as long as `diverges` holds: a line at a time, or a character at a time
+/// for inputs of a single line. Whole chunks are tried first, halving down to single pieces.
+pub fn minimize(input: &str, diverges: impl Fn(&str) -> bool) -> String {
+    let mut pieces = if input.trim_end().contains('\n') {
+        input.split_inclusive('\n').collect::<Vec<_>>()
+    } else {
+        input
+            .char_indices()
+            .map(|(idx, c)| &input[idx..idx + c.len_utf8()])
+            .collect()
+    };
+    let mut chunk = pieces.len().max(1);
+
+    loop {
+        let mut removed_any = false;
+        let mut start = 0;
+
+        while start < pieces.len() {
+            let end = (start + chunk).min(pieces.len());
+            let candidate = [&pieces[..start], &pieces[end..]].concat().concat();
+
+            if diverges(&candidate) {
+                pieces.drain(start..end);
+                removed_any = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if chunk > 1 {
+            chunk /= 2;
+        } else if !removed_any {
+            return pieces.concat();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Generate, Generated, Rng};
+
+    /// Counts the lines of its input, each of which is a number.
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 1;
+
+        type Parsed<'a> = Vec<u64>;
+        type Answer1 = usize;
+        type Answer2 = u64;
+        type Error = std::num::ParseIntError;
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+            input.lines().map(str::parse).collect()
+        }
+
+        fn part1(numbers: &Self::Parsed<'_>) -> Self::Answer1 {
+            numbers.len()
+        }
+
+        fn part2(numbers: &Self::Parsed<'_>) -> Self::Answer2 {
+            numbers.iter().sum()
+        }
+    }
+
+    impl Generate for Lines {
+        fn generate(rng: &mut Rng, size: usize) -> Generated<usize, u64> {
+            let numbers = (0..size).map(|_| rng.range(0..=20)).collect::<Vec<_>>();
+
+            Generated {
+                input: numbers.iter().map(|n| format!("{}\n", n)).collect(),
+                answer1: size,
+                answer2: numbers.iter().sum(),
+            }
+        }
+    }
+
+    /// Gets the sum wrong as soon as a number above 15 shows up.
+    fn capped_sum(input: &str) -> Result<Answer, String> {
+        let numbers = Lines::parse(input).map_err(|e| e.to_string())?;
+
+        Ok(Answer::from(
+            numbers.iter().map(|&n| n.min(15)).sum::<u64>(),
+        ))
+    }
+
+    #[test]
+    fn test_cross_check_agreeing() {
+        let implementations = [
+            Implementation::solution::<Lines>(Part::One),
+            Implementation::solution::<Lines>(Part::Two),
+            Implementation::new(Part::One, "counting newlines", |input| {
+                Ok(Answer::from(input.matches('\n').count()))
+            }),
+        ];
+
+        assert!(cross_check(&Lines, &implementations, 0..20, 50).is_ok());
+    }
+
+    #[test]
+    fn test_cross_check_minimizes() {
+        let implementations = [
+            Implementation::solution::<Lines>(Part::Two),
+            Implementation::new(Part::Two, "capped", capped_sum),
+        ];
+
+        let divergence = cross_check(&Lines, &implementations, 0..20, 50).unwrap_err();
+
+        assert_eq!(divergence.part, Part::Two);
+        assert_eq!(divergence.seed, 0);
+        assert_eq!(divergence.input.lines().count(), 1);
+        assert!(divergence.input.trim().parse::<u64>().unwrap() > 15);
+        assert!(!divergence.outcomes[0]
+            .1
+            .agrees_with(&divergence.outcomes[1].1));
+    }
+
+    #[test]
+    fn test_cross_check_against_generator() {
+        let implementations = [Implementation::new(Part::One, "off by one", |input| {
+            Ok(Answer::from(input.lines().count() + 1))
+        })];
+
+        let divergence = cross_check(&Lines, &implementations, 0..1, 10).unwrap_err();
+
+        assert_eq!(
+            divergence.outcomes[0],
+            ("generated", Outcome::Answer(Answer::Number(10)))
+        );
+        assert_eq!(divergence.input.lines().count(), 10);
+    }
+
+    #[test]
+    fn test_run_catches_panics() {
+        let implementation = Implementation::new(Part::One, "panicking", |_| panic!("oops"));
+
+        assert_eq!(implementation.run(""), Outcome::Panic(String::from("oops")));
+        assert!(Outcome::Panic(String::from("a")).agrees_with(&Outcome::Panic(String::from("b"))));
+        assert!(!Outcome::Panic(String::from("a")).agrees_with(&Outcome::Error(String::from("a"))));
+    }
+
+    #[test]
+    fn test_minimize() {
+        let minimized = minimize("1\n2\n3\n4\n5\n6\n", |input| input.contains("4\n"));
+        assert_eq!(minimized, "4\n");
+
+        let minimized = minimize("abcdefabcdef", |input| {
+            input.contains('c') && input.contains('e')
+        });
+        assert_eq!(minimized, "ce");
+    }
+}
@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// The byte range `part` occupies in `whole`, if it is a slice of it.
+///
+/// This stands in for the unstable `str::substr_range`.
+pub fn substr_range(whole: &str, part: &str) -> Option<Range<usize>> {
+    let start = (part.as_ptr() as usize).checked_sub(whole.as_ptr() as usize)?;
+    let end = start + part.len();
+
+    (end <= whole.len()).then_some(start..end)
+}
+
+/// An error of kind `E` about the bytes `span` of the string that was being parsed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Spanned<E> {
+    pub kind: E,
+    pub span: Range<usize>,
+}
+
+impl<E> Spanned<E> {
+    /// An error about `part`, which should be a slice of `whole`. If it isn't, the error is
+    /// about all of `whole`.
+    pub fn at(kind: E, whole: &str, part: &str) -> Self {
+        Spanned {
+            kind,
+            span: substr_range(whole, part).unwrap_or(0..whole.len()),
+        }
+    }
+
+    /// Moves an error from parsing `part` so that its span is relative to `whole` instead.
+    pub fn within(self, whole: &str, part: &str) -> Self {
+        let offset = substr_range(whole, part).map_or(0, |range| range.start);
+
+        Spanned {
+            kind: self.kind,
+            span: self.span.start + offset..self.span.end + offset,
+        }
+    }
+
+    /// Resolves the span against the full puzzle `input` into a line and columns.
+    pub fn locate(self, input: &str) -> InputError<E> {
+        let start = self.span.start.min(input.len());
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let end = self.span.end.clamp(start, line_end);
+
+        InputError {
+            kind: self.kind,
+            line: input[..start].matches('\n').count() + 1,
+            columns: start - line_start..end - line_start,
+            text: input[line_start..line_end].to_owned(),
+        }
+    }
+}
+
+/// A parse error pinned to the line of the puzzle input it came from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InputError<E> {
+    pub kind: E,
+    /// The 1-based line number.
+    pub line: usize,
+    /// The bytes of `text` the error is about.
+    pub columns: Range<usize>,
+    /// The whole offending line.
+    pub text: String,
+}
+
+impl<E: fmt::Display> fmt::Display for InputError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = self.text[..self.columns.start].chars().count() + 1;
+        let width = self.text[self.columns.clone()].chars().count().max(1);
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "{}", self.kind)?;
+        writeln!(f, "{}--> line {}, column {}", gutter, self.line, column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for InputError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.kind.source()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "move 1 from 2 to 1\nmove x from 1 to 3\nmove 2 from 2 to 1";
+
+    #[test]
+    fn test_substr_range() {
+        let line = INPUT.lines().nth(1).unwrap();
+
+        assert_eq!(substr_range(INPUT, line), Some(19..37));
+        assert_eq!(substr_range(line, INPUT), None);
+        assert_eq!(substr_range(INPUT, "move"), None);
+    }
+
+    #[test]
+    fn test_locate() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let token = line.split_ascii_whitespace().nth(1).unwrap();
+
+        let error = Spanned::at("invalid number", line, token)
+            .within(INPUT, line)
+            .locate(INPUT);
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 5..6);
+        assert_eq!(error.text, "move x from 1 to 3");
+    }
+
+    #[test]
+    fn test_locate_end_of_input() {
+        let error = Spanned::at("missing procedures", INPUT, &INPUT[INPUT.len()..]).locate(INPUT);
+
+        assert_eq!(error.line, 3);
+        assert_eq!(error.columns, 18..18);
+    }
+
+    #[test]
+    fn test_display() {
+        let error = Spanned {
+            kind: "invalid number \"x\"",
+            span: 24..25,
+        }
+        .locate(INPUT);
+
+        assert_eq!(
+            error.to_string(),
+            "invalid number \"x\"\n --> line 2, column 6\n  |\n2 | move x from 1 to 3\n  |      ^"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{Answer, Rng, Solution};
+
+/// A generated puzzle input, with the answers it was built to have.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Generated<A1, A2> {
+    pub input: String,
+    pub answer1: A1,
+    pub answer2: A2,
+}
+
+/// A [`Solution`] that can make up inputs of its own, whose answers are known without solving
+/// them.
+pub trait Generate: Solution {
+    /// Generates an input from `rng`. What `size` counts depends on the day (elves, rounds,
+    /// procedures...), but bigger is always more input.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<Self::Answer1, Self::Answer2>;
+}
+
+/// A type-erased [`Generate`], so that generators can be registered together like solvers.
+pub trait Generator: Sync {
+    fn day(&self) -> u8;
+
+    /// The same `seed` and `size` always give the same input.
+    fn generate(&self, seed: u64, size: usize) -> Generated<Answer, Answer>;
+}
+
+impl<G: Generate + Sync> Generator for G {
+    fn day(&self) -> u8 {
+        G::DAY
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Generated<Answer, Answer> {
+        let generated = G::generate(&mut Rng::new(seed), size);
+
+        Generated {
+            input: generated.input,
+            answer1: generated.answer1.into(),
+            answer2: generated.answer2.into(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub enum InputSource {
+    /// The `input.txt` checked in next to the day's crate.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for InputSource {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+impl InputSource {
+    /// Picks the source named by an `--input <path>` argument, if there is one.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--input" || arg == "-i" {
+                if let Some(value) = args.next() {
+                    return InputSource::from(value.as_str());
+                }
+            }
+        }
+
+        InputSource::Default
+    }
+
+    /// Reads the input and [`normalize`]s it.
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        let raw = match self {
+            InputSource::Default => fs::read_to_string(default_path(day))?,
+            InputSource::Path(path) => fs::read_to_string(path)?,
+            InputSource::Stdin => {
+                let mut raw = String::new();
+                io::stdin().read_to_string(&mut raw)?;
+                raw
+            }
+        };
+
+        Ok(normalize(&raw))
+    }
+
+    pub fn describe(&self, day: u8) -> String {
+        match self {
+            InputSource::Default => default_path(day).display().to_string(),
+            InputSource::Path(path) => path.display().to_string(),
+            InputSource::Stdin => String::from("stdin"),
+        }
+    }
+}
+
+/// The root directory of this workspace.
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate lives inside the workspace")
+        .to_path_buf()
+}
+
+/// The path of `dayNN/input.txt` in this workspace.
+pub fn default_path(day: u8) -> PathBuf {
+    workspace_root()
+        .join(format!("day{:02}", day))
+        .join("input.txt")
+}
+
+/// Puts an input into the one shape every day's parser expects, whatever it was saved with: no
+/// byte order mark, `\n` line endings, and no trailing newlines or other whitespace at the end.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut input = raw.replace("\r\n", "\n").replace('\r', "\n");
+    input.truncate(input.trim_end().len());
+
+    input
+}
+
+/// The sections of a normalized input, which are separated by blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input.split("\n\n")
+}
+
+/// A 64-bit FNV-1a hash of `input`, which unlike `DefaultHasher` is stable across Rust
+/// releases and so can be written to disk.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Reads the input for `day` from the source given on this process's command line.
+pub fn from_args(day: u8) -> io::Result<String> {
+    InputSource::from_args(env::args().skip(1)).read(day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_source_from_args() {
+        assert_eq!(InputSource::from_args(args("")), InputSource::Default);
+        assert_eq!(
+            InputSource::from_args(args("--input other.txt")),
+            InputSource::Path(PathBuf::from("other.txt"))
+        );
+        assert_eq!(InputSource::from_args(args("-i -")), InputSource::Stdin);
+    }
+
+    #[test]
+    fn test_default_path() {
+        let path = default_path(5);
+
+        assert!(path.ends_with("day05/input.txt"));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+        assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(hash("1000\n2000"), hash("1000\n2001"));
+    }
+
+    #[test]
+    fn test_read_from_path() {
+        let path = env::temp_dir().join(format!("aoc-input-test-{}.txt", std::process::id()));
+        fs::write(&path, "1000\n2000\n").unwrap();
+
+        let input = InputSource::Path(path.clone()).read(1);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.unwrap(), "1000\n2000");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1000\n2000\n\n3000"), "1000\n2000\n\n3000");
+        assert_eq!(
+            normalize("\u{feff}1000\r\n2000\r\n\r\n3000\r\n\r\n"),
+            "1000\n2000\n\n3000"
+        );
+        assert_eq!(normalize("A Y\rB X\r"), "A Y\nB X");
+        assert_eq!(
+            normalize("mjqjpqmgbljsphdztnvjfqwrcgsmlb \n\n"),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb"
+        );
+        assert_eq!(normalize("    [D]    \n 1 "), "    [D]    \n 1");
+        assert_eq!(normalize("\u{feff}\n"), "");
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = normalize("1000\r\n2000\r\n\r\n3000\r\n");
+
+        assert_eq!(sections(&input).collect::<Vec<_>>(), ["1000\n2000", "3000"]);
+        assert_eq!(sections("").collect::<Vec<_>>(), [""]);
+    }
+}
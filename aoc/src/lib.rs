@@ -0,0 +1,15 @@
+pub use error::{substr_range, InputError, Spanned};
+pub use generate::{Generate, Generated, Generator};
+pub use rng::Rng;
+pub use solution::{Answer, Solution};
+pub use solver::{Part, PartRun, Run, Solver};
+pub use stream::{LineReader, StreamError, Streaming};
+
+pub mod differential;
+mod error;
+mod generate;
+pub mod input;
+mod rng;
+mod solution;
+mod solver;
+mod stream;
@@ -0,0 +1,99 @@
+use std::ops::RangeInclusive;
+
+/// A small seeded random number generator (SplitMix64).
+///
+/// It is nowhere near good enough for anything secret, but the same seed always gives the same
+/// numbers on every platform, which is what generated inputs need.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+
+        let span = u128::from(end - start) + 1;
+        start + ((u128::from(self.next_u64()) * span) >> 64) as u64
+    }
+
+    /// An index below `len`, which must not be zero.
+    pub fn below(&mut self, len: usize) -> usize {
+        assert!(len > 0, "nothing to pick from");
+
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// True with a chance of `numerator` in `denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(1..=denominator) <= numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let a = (0..8).map(|_| a.next_u64()).collect::<Vec<_>>();
+        let b = (0..8).map(|_| b.next_u64()).collect::<Vec<_>>();
+        let c = (0..8).map(|_| c.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(1);
+        let samples = (0..1000).map(|_| rng.range(3..=6)).collect::<Vec<_>>();
+
+        assert!(samples.iter().all(|n| (3..=6).contains(n)));
+        assert!((3..=6).all(|n| samples.contains(&n)));
+        assert_eq!(rng.range(7..=7), 7);
+        assert!(rng.range(0..=u64::MAX) > 0);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(7);
+        let mut items = (0..20).collect::<Vec<_>>();
+
+        rng.shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort();
+
+        assert_ne!(items, sorted);
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// A day's puzzle, split into parsing the input and solving each part from the parsed form.
+pub trait Solution {
+    /// The day of the month (1-25) this solution is for.
+    const DAY: u8;
+
+    /// The parsed puzzle input, which may borrow from the raw input.
+    type Parsed<'a>;
+
+    type Answer1: Into<Answer> + fmt::Display;
+    type Answer2: Into<Answer> + fmt::Display;
+
+    /// Why the raw input could not be parsed.
+    type Error: Error + Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Self::Answer1;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Self::Answer2;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(24000usize).to_string(), "24000");
+        assert_eq!(Answer::from(String::from("CMZ")).to_string(), "CMZ");
+    }
+}
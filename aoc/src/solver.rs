@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::{Answer, Solution};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("unknown part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+/// The answers from one run of a [`Solver`], with how long each phase took.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// A type-erased [`Solution`], so that days can be registered together and picked at runtime.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    /// Parses `input` once and solves each of the requested parts, in order.
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Run, Box<dyn Error + Send + Sync>>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Run, Box<dyn Error + Send + Sync>> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_time = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => S::part1(&parsed).into(),
+                    Part::Two => S::part2(&parsed).into(),
+                };
+
+                PartRun {
+                    part,
+                    answer,
+                    time: start.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(Run { parse_time, parts })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::mem;
+
+use crate::{InputError, Solution, Spanned};
+
+/// A [`Solution`] that can also solve both parts while reading its input a piece at a time, for
+/// inputs too large to hold in memory.
+pub trait Streaming: Solution {
+    /// What goes wrong parsing a piece of the input.
+    type ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<Answers<Self>, StreamError<Self::ParseError>>;
+}
+
+type Answers<S> = (<S as Solution>::Answer1, <S as Solution>::Answer2);
+
+/// Why a streamed input could not be solved.
+#[derive(Debug)]
+pub enum StreamError<E> {
+    Io(io::Error),
+    Input(InputError<E>),
+    /// Something wrong with the input as a whole rather than with any one line of it.
+    Invalid(E),
+}
+
+impl<E: fmt::Display> fmt::Display for StreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "could not read the input: {}", e),
+            StreamError::Input(e) => write!(f, "{}", e),
+            StreamError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for StreamError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(e) => Some(e),
+            StreamError::Input(e) => e.source(),
+            StreamError::Invalid(e) => e.source(),
+        }
+    }
+}
+
+impl<E> From<io::Error> for StreamError<E> {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+/// Reads lines into a reused buffer, so only the longest line has to fit in memory.
+pub struct LineReader<R> {
+    reader: R,
+    buf: String,
+    number: usize,
+    /// The first line after a run of blank lines, read to find out whether the run ends the
+    /// input. Only meaningful while `has_ahead` is set.
+    ahead: String,
+    has_ahead: bool,
+    /// How many blank lines of that run are still to be returned before `ahead`.
+    blank: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        LineReader {
+            reader,
+            buf: String::new(),
+            number: 0,
+            ahead: String::new(),
+            has_ahead: false,
+            blank: 0,
+        }
+    }
+
+    /// The next line without its line ending, or `None` at the end of the input. A byte order
+    /// mark at the very start and blank lines at the very end are dropped, as
+    /// [`crate::input::normalize`] would.
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        self.buf.clear();
+
+        if self.blank > 0 {
+            self.blank -= 1;
+        } else if self.has_ahead {
+            mem::swap(&mut self.buf, &mut self.ahead);
+            self.has_ahead = false;
+        } else {
+            if self.reader.read_line(&mut self.buf)? == 0 {
+                return Ok(None);
+            }
+            if self.number == 0 && self.buf.starts_with('\u{feff}') {
+                self.buf.drain(..'\u{feff}'.len_utf8());
+            }
+
+            if is_blank(&self.buf) {
+                loop {
+                    self.ahead.clear();
+                    if self.reader.read_line(&mut self.ahead)? == 0 {
+                        self.buf.clear();
+                        self.blank = 0;
+                        return Ok(None);
+                    }
+                    if !is_blank(&self.ahead) {
+                        break;
+                    }
+                    self.blank += 1;
+                }
+                self.has_ahead = true;
+                self.buf.clear();
+            }
+        }
+        self.number += 1;
+
+        Ok(Some(self.line()))
+    }
+
+    /// The 1-based number of the line last returned by [`LineReader::next_line`].
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// Pins an error about the line last returned, with its span relative to that line, to
+    /// where the line is in the input.
+    pub fn locate<E>(&self, error: Spanned<E>) -> StreamError<E> {
+        let mut error = error.locate(self.line());
+        error.line = self.number;
+
+        StreamError::Input(error)
+    }
+
+    fn line(&self) -> &str {
+        let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+}
+
+/// Whether a line read with its line ending is empty.
+fn is_blank(line: &str) -> bool {
+    matches!(line, "\n" | "\r\n" | "\r")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_line() {
+        let mut lines = LineReader::new("\u{feff}1000\r\n2000\n\n3000".as_bytes());
+        let mut read = Vec::new();
+
+        while let Some(line) = lines.next_line().unwrap() {
+            read.push(line.to_owned());
+        }
+
+        assert_eq!(read, ["1000", "2000", "", "3000"]);
+        assert_eq!(lines.number(), 4);
+    }
+
+    #[test]
+    fn test_next_line_drops_trailing_blank_lines() {
+        let read = |input: &str| {
+            let mut lines = LineReader::new(input.as_bytes());
+            let mut read = Vec::new();
+
+            while let Some(line) = lines.next_line().unwrap() {
+                let line = line.to_owned();
+                read.push((lines.number(), line));
+            }
+            read
+        };
+
+        assert_eq!(
+            read("1000\n\n\r\n2000\n\n\r\n\n"),
+            [
+                (1, String::from("1000")),
+                (2, String::new()),
+                (3, String::new()),
+                (4, String::from("2000"))
+            ]
+        );
+        assert_eq!(
+            read("\u{feff}\n1000"),
+            [(1, String::new()), (2, String::from("1000"))]
+        );
+        assert!(read("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_locate() {
+        let mut lines = LineReader::new("move 1 from 2 to 1\nmove x from 1 to 3\n".as_bytes());
+        lines.next_line().unwrap();
+        let line = lines.next_line().unwrap().unwrap();
+        let error = Spanned::at("invalid number", line, &line[5..6]);
+
+        let StreamError::Input(error) = lines.locate(error) else {
+            panic!("expected an input error");
+        };
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 5..6);
+        assert_eq!(error.text, "move x from 1 to 3");
+    }
+}
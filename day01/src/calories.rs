@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::ParseIntError;
+
+/// A type to add up calories in. Items are parsed into the same type, and adding says so when the
+/// sum doesn't fit rather than wrapping around.
+pub trait Calories: Ord + Clone + fmt::Debug + fmt::Display {
+    /// The type's name, for errors about totals that don't fit.
+    const NAME: &'static str;
+
+    fn zero() -> Self;
+
+    fn parse_item(item: &str) -> Result<Self, ParseIntError>;
+
+    /// `None` when `self + item` doesn't fit.
+    fn checked_add_item(self, item: Self) -> Option<Self>;
+}
+
+/// Implements [`Calories`] for primitive unsigned integers, which already have everything it
+/// needs.
+macro_rules! impl_calories {
+    ($($int:ty),*) => {
+        $(
+            impl Calories for $int {
+                const NAME: &'static str = stringify!($int);
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn parse_item(item: &str) -> Result<Self, ParseIntError> {
+                    item.parse()
+                }
+
+                fn checked_add_item(self, item: Self) -> Option<Self> {
+                    self.checked_add(item)
+                }
+            }
+        )*
+    };
+}
+
+impl_calories!(usize, u64, u128);
+
+/// An unsigned integer of any size, for generated inputs whose items or totals outgrow even a
+/// `u128`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BigCalories {
+    /// Base 2^64 digits, least significant first, without leading zeros.
+    digits: Vec<u64>,
+}
+
+impl BigCalories {
+    /// `self * factor + term`.
+    fn mul_add(mut self, factor: u64, term: u64) -> Self {
+        let mut carry = u128::from(term);
+
+        for digit in &mut self.digits {
+            let value = u128::from(*digit) * u128::from(factor) + carry;
+            *digit = value as u64;
+            carry = value >> 64;
+        }
+        if carry > 0 {
+            self.digits.push(carry as u64);
+        }
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+
+        self
+    }
+}
+
+impl Calories for BigCalories {
+    const NAME: &'static str = "BigCalories";
+
+    fn zero() -> Self {
+        BigCalories::default()
+    }
+
+    fn parse_item(item: &str) -> Result<Self, ParseIntError> {
+        let digits = item.strip_prefix('+').unwrap_or(item);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            // Not a number of any size, which a u64 rejects with the same error.
+            return item.parse::<u64>().map(BigCalories::from);
+        }
+
+        Ok(digits.bytes().fold(BigCalories::zero(), |n, digit| {
+            n.mul_add(10, u64::from(digit - b'0'))
+        }))
+    }
+
+    fn checked_add_item(self, item: Self) -> Option<Self> {
+        let (mut sum, other) = if self.digits.len() >= item.digits.len() {
+            (self, item)
+        } else {
+            (item, self)
+        };
+        let mut carry = 0;
+
+        for (idx, digit) in sum.digits.iter_mut().enumerate() {
+            let value = u128::from(*digit)
+                + u128::from(other.digits.get(idx).copied().unwrap_or(0))
+                + carry;
+            *digit = value as u64;
+            carry = value >> 64;
+        }
+        if carry > 0 {
+            sum.digits.push(carry as u64);
+        }
+
+        Some(sum)
+    }
+}
+
+impl From<u64> for BigCalories {
+    fn from(n: u64) -> Self {
+        BigCalories::zero().mul_add(1, n)
+    }
+}
+
+impl Ord for BigCalories {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigCalories {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigCalories {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peels off 19 decimal digits at a time, the most that fit in a u64.
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+
+        let mut digits = self.digits.clone();
+        let mut chunks = Vec::new();
+        while !digits.is_empty() {
+            let mut remainder = 0;
+            for digit in digits.iter_mut().rev() {
+                let value = (remainder << 64) | u128::from(*digit);
+                *digit = (value / CHUNK) as u64;
+                remainder = value % CHUNK;
+            }
+            chunks.push(remainder as u64);
+
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most, rest)) => {
+                write!(f, "{}", most)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:019}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 2^128 + 2^64, past what a u128 holds.
+    static BEYOND_U128: &str = "340282366920938463481821351505477763072";
+
+    #[test]
+    fn test_checked_add_item() {
+        assert_eq!(u64::MAX.checked_add_item(1), None);
+        assert_eq!(
+            (u64::MAX as u128).checked_add_item(1),
+            Some(u64::MAX as u128 + 1)
+        );
+        assert_eq!(u128::MAX.checked_add_item(1), None);
+    }
+
+    #[test]
+    fn test_big_calories() {
+        assert_eq!(BigCalories::zero().to_string(), "0");
+        assert_eq!(BigCalories::parse_item("007").unwrap().to_string(), "7");
+
+        let max = BigCalories::from(u64::MAX);
+        let mut total = BigCalories::zero();
+        for _ in 0..4 {
+            total = total.checked_add_item(max.clone()).unwrap();
+        }
+        assert_eq!(total.to_string(), (4 * u64::MAX as u128).to_string());
+        assert!(total > max);
+
+        let beyond = BigCalories::parse_item(BEYOND_U128).unwrap();
+        assert_eq!(
+            beyond,
+            BigCalories {
+                digits: vec![0, 1, 1]
+            }
+        );
+        assert_eq!(beyond.to_string(), BEYOND_U128);
+        assert!(beyond > total);
+
+        let sum = beyond.clone().checked_add_item(beyond).unwrap();
+        assert_eq!(sum.digits, [0, 2, 2]);
+    }
+
+    #[test]
+    fn test_big_calories_parse_errors() {
+        for item in ["", "+", "12a", "-1", " 1"] {
+            assert_eq!(
+                BigCalories::parse_item(item).unwrap_err(),
+                item.parse::<u64>().unwrap_err()
+            );
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use aoc::input::sections;
+use aoc::{InputError, Spanned};
+
+use crate::{parse_calories, Calories, ParseError};
+
+/// One elf's inventory: every item's calories, and where the elf comes in the input, counting
+/// from 0.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Elf {
+    pub index: usize,
+    pub items: Vec<u64>,
+}
+
+impl Elf {
+    /// Parses every elf in `input`. Inventories whose calories add up to more than a `u64` holds
+    /// are rejected, so the totals of the elves that are returned never overflow.
+    pub fn parse_all(input: &str) -> Result<Vec<Elf>, InputError<ParseError>> {
+        sections(input)
+            .enumerate()
+            .map(|(index, elf)| Elf::parse(index, elf).map_err(|e| e.within(input, elf)))
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.locate(input))
+    }
+
+    fn parse(index: usize, elf: &str) -> Result<Elf, Spanned<ParseError>> {
+        let mut total = 0u64;
+        let items = elf
+            .lines()
+            .map(|item| {
+                let calories = parse_calories(item).map_err(|e| e.within(elf, item))?;
+                total = total.checked_add_item(calories).ok_or_else(|| {
+                    let kind = ParseError::CaloriesOverflow {
+                        elf: index,
+                        limit: u64::NAME,
+                    };
+
+                    Spanned::at(kind, elf, item)
+                })?;
+
+                Ok(calories)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Elf { index, items })
+    }
+
+    pub fn total_calories(&self) -> u64 {
+        self.items.iter().sum()
+    }
+
+    /// The item with the most calories, or `None` for an elf carrying nothing.
+    pub fn largest_item(&self) -> Option<u64> {
+        self.items.iter().copied().max()
+    }
+}
+
+/// Names the elf the way the puzzle does, counting from 1.
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "elf {} ({} calories in {} items)",
+            self.index + 1,
+            self.total_calories(),
+            self.items.len()
+        )
+    }
+}
+
+/// The elf carrying the most calories. Between elves carrying as many, the first one.
+pub fn most_calories(elves: &[Elf]) -> Option<&Elf> {
+    elves.iter().reduce(|most, elf| {
+        if elf.total_calories() > most.total_calories() {
+            elf
+        } else {
+            most
+        }
+    })
+}
+
+/// The elves carrying more than `calories` in total, in input order.
+pub fn carrying_more_than(elves: &[Elf], calories: u64) -> impl Iterator<Item = &Elf> {
+    elves
+        .iter()
+        .filter(move |elf| elf.total_calories() > calories)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SAMPLE_INPUT;
+
+    #[test]
+    fn test_parse_all() {
+        let elves = Elf::parse_all(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(elves.len(), 5);
+        assert_eq!(
+            elves[3],
+            Elf {
+                index: 3,
+                items: vec![7000, 8000, 9000]
+            }
+        );
+        assert_eq!(Elf::parse_all("").unwrap()[0].items, []);
+
+        let error = Elf::parse_all("1000\n\n2000\n2x00").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (4, "2x00"));
+
+        let input = format!("1\n\n{}\n1", u64::MAX);
+        let error = Elf::parse_all(&input).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseError::CaloriesOverflow {
+                elf: 1,
+                limit: "u64"
+            }
+        );
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn test_most_calories() {
+        let elves = Elf::parse_all(SAMPLE_INPUT).unwrap();
+        let most = most_calories(&elves).unwrap();
+
+        assert_eq!(most.index, 3);
+        assert_eq!(most.to_string(), "elf 4 (24000 calories in 3 items)");
+        assert_eq!(most.largest_item(), Some(9000));
+
+        let elves = Elf::parse_all("5\n\n7\n\n7").unwrap();
+        assert_eq!(most_calories(&elves).unwrap().index, 1);
+        assert_eq!(most_calories(&[]), None);
+    }
+
+    #[test]
+    fn test_carrying_more_than() {
+        let elves = Elf::parse_all(SAMPLE_INPUT).unwrap();
+        let indices = |calories| {
+            carrying_more_than(&elves, calories)
+                .map(|elf| elf.index)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(indices(10000), [2, 3]);
+        assert_eq!(indices(0), [0, 1, 2, 3, 4]);
+        assert_eq!(indices(24000), []);
+    }
+}
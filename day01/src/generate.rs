@@ -0,0 +1,33 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::Day01;
+
+impl Generate for Day01 {
+    /// `size` is the number of elves, each carrying 1 to 15 snacks.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<usize, usize> {
+        let mut totals = Vec::with_capacity(size);
+
+        let elves = (0..size.max(1))
+            .map(|_| {
+                let items = (0..rng.range(1..=15))
+                    .map(|_| rng.range(1000..=60000))
+                    .collect::<Vec<_>>();
+                totals.push(items.iter().sum::<u64>() as usize);
+
+                items
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>();
+
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+
+        Generated {
+            input: elves.join("\n\n") + "\n",
+            answer1: totals[0],
+            answer2: totals.iter().take(3).sum(),
+        }
+    }
+}
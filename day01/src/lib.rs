@@ -0,0 +1,509 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::num::ParseIntError;
+
+use aoc::differential::Implementation;
+use aoc::input::sections;
+use aoc::{Answer, InputError, LineReader, Part, Solution, Spanned, StreamError, Streaming};
+
+pub use calories::{BigCalories, Calories};
+pub use elf::{carrying_more_than, most_calories, Elf};
+pub use rebalance::{longest_processing_time, rebalance, Plan, Rebalancing};
+pub use stats::{calorie_stats, Bucket, CalorieStats, PERCENTILES};
+
+mod calories;
+mod elf;
+mod generate;
+mod rebalance;
+mod stats;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    InvalidCalories {
+        item: String,
+        source: ParseIntError,
+    },
+    /// The elf at `elf`, counting from 0, carries more calories than a `limit` holds.
+    CaloriesOverflow {
+        elf: usize,
+        limit: &'static str,
+    },
+    /// The top three elves carry more calories together than a `usize` holds. When the whole
+    /// input is at hand, the error points at the third of them.
+    TopThreeOverflow,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidCalories { item, .. } => {
+                write!(f, "invalid calorie count {:?}", item)
+            }
+            ParseError::CaloriesOverflow { elf, limit } => write!(
+                f,
+                "elf {} carries more calories than a {} can hold",
+                elf + 1,
+                limit
+            ),
+            ParseError::TopThreeOverflow => write!(
+                f,
+                "the top three elves carry more than {} calories together",
+                usize::MAX
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidCalories { source, .. } => Some(source),
+            ParseError::CaloriesOverflow { .. } | ParseError::TopThreeOverflow => None,
+        }
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<ElfTotal>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = InputError<ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        let top = top_n_calories(input, 3)?;
+
+        // Makes sure part 2 can add up the top three without overflowing.
+        if checked_sum(top.iter().map(|total| total.calories)).is_none() {
+            let third = top.last().map_or(input, |total| {
+                sections(input).nth(total.index).unwrap_or(input)
+            });
+            return Err(Spanned::at(ParseError::TopThreeOverflow, input, third).locate(input));
+        }
+
+        Ok(top)
+    }
+
+    fn part1(calories: &Self::Parsed<'_>) -> Self::Answer1 {
+        max_calories_carried(calories)
+    }
+
+    fn part2(calories: &Self::Parsed<'_>) -> Self::Answer2 {
+        top_three_calories_sum(calories)
+    }
+}
+
+impl Streaming for Day01 {
+    type ParseError = ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseError>> {
+        let mut lines = LineReader::new(reader);
+        let mut top_three = [0; 3];
+        let mut current_elf = 0;
+        let mut elf = 0;
+
+        while let Some(line) = lines.next_line()? {
+            if line.is_empty() {
+                keep_top_three(&mut top_three, current_elf);
+                current_elf = 0;
+                elf += 1;
+                continue;
+            }
+
+            current_elf = add_calories(current_elf, line, elf).map_err(|e| lines.locate(e))?;
+        }
+        keep_top_three(&mut top_three, current_elf);
+
+        // The elves' lines are long gone by now, so there is nowhere in the input to point at.
+        let sum =
+            checked_sum(top_three).ok_or(StreamError::Invalid(ParseError::TopThreeOverflow))?;
+
+        Ok((top_three[0], sum))
+    }
+}
+
+/// Keeps the three largest totals seen so far in `top_three`, largest first.
+fn keep_top_three(top_three: &mut [usize; 3], total: usize) {
+    if let Some(idx) = top_three.iter().position(|&top| total > top) {
+        top_three[idx..].rotate_right(1);
+        top_three[idx] = total;
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day01>(Part::One),
+    Implementation::solution::<Day01>(Part::Two),
+    Implementation::streaming::<Day01>(Part::One),
+    Implementation::streaming::<Day01>(Part::Two),
+    Implementation::new(Part::One, "sorted", |input| {
+        let calories = calories_by_elf_sorted(input).map_err(|e| e.locate(input).to_string())?;
+        Ok(Answer::from(calories[0]))
+    }),
+    Implementation::new(Part::Two, "sorted", |input| {
+        let calories = calories_by_elf_sorted(input).map_err(|e| e.locate(input).to_string())?;
+        let sum = checked_sum(calories.iter().copied().take(3))
+            .ok_or_else(|| ParseError::TopThreeOverflow.to_string())?;
+        Ok(Answer::from(sum))
+    }),
+];
+
+/// An elf's total calories, and where the elf comes in the input, counting from 0.
+///
+/// Totals are ordered by calories, and between equal totals the elf that comes first is the
+/// larger, so that ties always go the same way.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfTotal<C = usize> {
+    pub index: usize,
+    pub calories: C,
+}
+
+impl<C: Ord> Ord for ElfTotal<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.calories
+            .cmp(&other.calories)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<C: Ord> PartialOrd for ElfTotal<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The `n` elves carrying the most calories, most first. Fewer when there are fewer elves.
+///
+/// Only the best `n` totals so far are kept, in a min-heap, so this takes O(E log n) for E elves
+/// rather than sorting all of them.
+pub fn top_n_calories(input: &str, n: usize) -> Result<Vec<ElfTotal>, InputError<ParseError>> {
+    top_n_calories_as(input, n)
+}
+
+/// [`top_n_calories`], adding up each elf's calories in a `C`. A wider `C` takes inputs whose
+/// totals don't fit in a `usize`, and [`BigCalories`] takes any input at all.
+pub fn top_n_calories_as<C: Calories>(
+    input: &str,
+    n: usize,
+) -> Result<Vec<ElfTotal<C>>, InputError<ParseError>> {
+    top_n(elf_totals(input), n).map_err(|e| e.locate(input))
+}
+
+fn top_n<C: Ord, E>(
+    totals: impl Iterator<Item = Result<C, E>>,
+    n: usize,
+) -> Result<Vec<ElfTotal<C>>, E> {
+    let mut heap = BinaryHeap::new();
+
+    for (index, calories) in totals.enumerate() {
+        heap.push(Reverse(ElfTotal {
+            index,
+            calories: calories?,
+        }));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect())
+}
+
+/// Every elf's total calories, in input order.
+fn elf_totals<C: Calories>(
+    input: &str,
+) -> impl Iterator<Item = Result<C, Spanned<ParseError>>> + '_ {
+    sections(input).enumerate().map(move |(elf, items)| {
+        items.lines().try_fold(C::zero(), |total, item| {
+            add_calories(total, item, elf).map_err(|e| e.within(input, item))
+        })
+    })
+}
+
+/// Every elf's total, largest first. Only kept to check [`top_n_calories`] against.
+fn calories_by_elf_sorted(input: &str) -> Result<Vec<usize>, Spanned<ParseError>> {
+    let mut calories_by_elf = elf_totals(input).collect::<Result<Vec<usize>, _>>()?;
+
+    calories_by_elf.sort_by(|cal1, cal2| cal2.cmp(cal1));
+
+    Ok(calories_by_elf)
+}
+
+/// Adds `item` to the running `total` of the elf at `elf`.
+fn add_calories<C: Calories>(total: C, item: &str, elf: usize) -> Result<C, Spanned<ParseError>> {
+    total
+        .checked_add_item(parse_calories(item)?)
+        .ok_or_else(|| {
+            let kind = ParseError::CaloriesOverflow {
+                elf,
+                limit: C::NAME,
+            };
+
+            Spanned::at(kind, item, item)
+        })
+}
+
+fn checked_sum(totals: impl IntoIterator<Item = usize>) -> Option<usize> {
+    totals
+        .into_iter()
+        .try_fold(0usize, |sum, total| sum.checked_add(total))
+}
+
+fn parse_calories<C: Calories>(item: &str) -> Result<C, Spanned<ParseError>> {
+    C::parse_item(item).map_err(|source| {
+        let kind = ParseError::InvalidCalories {
+            item: item.to_owned(),
+            source,
+        };
+
+        Spanned::at(kind, item, item)
+    })
+}
+
+/// The calories carried by the first of `top`, the totals [`top_n_calories`] picked.
+fn max_calories_carried(top: &[ElfTotal]) -> usize {
+    top[0].calories
+}
+
+/// Never overflows for totals [`Day01`] parsed, since parsing checks the sum fits.
+fn top_three_calories_sum(top: &[ElfTotal]) -> usize {
+    top.iter().take(3).map(|total| total.calories).sum()
+}
+
+/// The example from the puzzle, shared by every module's tests.
+#[cfg(test)]
+pub(crate) static SAMPLE_INPUT: &str = r#"1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"#;
+
+#[cfg(test)]
+mod test {
+    use aoc::differential::{cross_check, Outcome};
+    use aoc::{Part, Rng, Solution, StreamError, Streaming};
+
+    use crate::{
+        calories_by_elf_sorted, max_calories_carried, top_n_calories, top_n_calories_as,
+        top_three_calories_sum, BigCalories, Day01, ElfTotal, ParseError, IMPLEMENTATIONS,
+        SAMPLE_INPUT,
+    };
+
+    #[test]
+    fn test_invalid_calories() {
+        let error = calories_by_elf_sorted("1000\n2x00\n\n3000").unwrap_err();
+
+        assert!(matches!(
+            error.kind,
+            ParseError::InvalidCalories { ref item, .. } if item == "2x00"
+        ));
+        assert_eq!(error.span, 5..9);
+    }
+
+    #[test]
+    fn test_calories_overflow() {
+        let input = format!("1000\n\n{}\n1\n2000", usize::MAX);
+
+        let overflow = ParseError::CaloriesOverflow {
+            elf: 1,
+            limit: "usize",
+        };
+
+        let error = calories_by_elf_sorted(&input).unwrap_err();
+        assert_eq!(error.kind, overflow);
+        assert_eq!(&input[error.span], "1");
+
+        let error = Day01::solve_stream(input.as_bytes()).unwrap_err();
+        let StreamError::Input(error) = error else {
+            panic!("expected an input error, got {:?}", error);
+        };
+        assert_eq!((error.line, &error.kind), (4, &overflow));
+        assert_eq!(
+            error.kind.to_string(),
+            "elf 2 carries more calories than a usize can hold"
+        );
+    }
+
+    #[test]
+    fn test_top_three_overflow() {
+        let half = (usize::MAX / 2 + 1).to_string();
+        let input = [half.as_str(), "1", half.as_str()].join("\n\n");
+
+        let error = Day01::parse(&input).unwrap_err();
+        assert_eq!(error.kind, ParseError::TopThreeOverflow);
+        assert_eq!((error.line, error.text.as_str()), (3, "1"));
+
+        assert!(matches!(
+            Day01::solve_stream(input.as_bytes()),
+            Err(StreamError::Invalid(ParseError::TopThreeOverflow))
+        ));
+
+        assert!(IMPLEMENTATIONS
+            .iter()
+            .filter(|implementation| implementation.part == Part::Two)
+            .all(|implementation| matches!(implementation.run(&input), Outcome::Error(_))));
+    }
+
+    #[test]
+    fn test_top_n_calories_as() {
+        let input = format!("1000\n\n{}\n1\n2000", u64::MAX);
+
+        let top = top_n_calories_as::<u128>(&input, 1).unwrap();
+        assert_eq!(top[0].index, 1);
+        assert_eq!(top[0].calories, u64::MAX as u128 + 2001);
+
+        let input = format!("{}\n{}\n\n1", u128::MAX, u128::MAX);
+        let error = top_n_calories_as::<u128>(&input, 1).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseError::CaloriesOverflow {
+                elf: 0,
+                limit: "u128"
+            }
+        );
+        assert_eq!(error.line, 2);
+
+        let top = top_n_calories_as::<BigCalories>(&input, 2).unwrap();
+        assert_eq!(
+            top[0].calories.to_string(),
+            "680564733841876926926749214863536422910"
+        );
+        assert_eq!(top[1].calories, BigCalories::from(1));
+    }
+
+    #[test]
+    fn test_max_calories_carried() {
+        let top = top_n_calories(SAMPLE_INPUT, 3).unwrap();
+
+        assert_eq!(max_calories_carried(&top), 24000);
+    }
+
+    #[test]
+    fn test_top_three_calories_sum() {
+        let top = top_n_calories(SAMPLE_INPUT, 3).unwrap();
+
+        assert_eq!(top_three_calories_sum(&top), 45000);
+    }
+
+    #[test]
+    fn test_top_three_bounds() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..200 {
+            let input = (0..rng.range(1..=20))
+                .map(|_| {
+                    let items = (0..rng.range(1..=5)).map(|_| rng.range(0..=10000).to_string());
+                    items.collect::<Vec<_>>().join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let top = top_n_calories(&input, 3).unwrap();
+            let (max, top_three) = (max_calories_carried(&top), top_three_calories_sum(&top));
+
+            assert!(top.windows(2).all(|pair| pair[0] >= pair[1]));
+            assert!(max <= top_three && top_three <= 3 * max);
+        }
+    }
+
+    #[test]
+    fn test_top_n_calories() {
+        let total = |index, calories| ElfTotal { index, calories };
+
+        assert_eq!(
+            top_n_calories(SAMPLE_INPUT, 3).unwrap(),
+            [total(3, 24000), total(2, 11000), total(4, 10000)]
+        );
+        assert_eq!(
+            top_n_calories("1000\n\n2000", 3).unwrap(),
+            [total(1, 2000), total(0, 1000)]
+        );
+        assert_eq!(
+            top_n_calories("5\n\n7\n\n5\n\n5", 3).unwrap(),
+            [total(1, 7), total(0, 5), total(2, 5)]
+        );
+        assert!(top_n_calories(SAMPLE_INPUT, 0).unwrap().is_empty());
+        assert_eq!(top_n_calories(SAMPLE_INPUT, usize::MAX).unwrap().len(), 5);
+
+        let error = top_n_calories("1000\n\n2x00", 1).unwrap_err();
+        assert_eq!(error.line, 3);
+    }
+
+    #[test]
+    fn test_top_n_calories_matches_sorting() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..200 {
+            let input = (0..rng.range(1..=20))
+                .map(|_| rng.range(0..=30).to_string())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let n = rng.range(0..=25) as usize;
+
+            let top = top_n_calories(&input, n).unwrap();
+            let sorted = calories_by_elf_sorted(&input).unwrap();
+
+            assert_eq!(top.len(), n.min(sorted.len()));
+            assert!(top
+                .iter()
+                .map(|total| total.calories)
+                .eq(sorted.into_iter().take(n)));
+            assert!(top.windows(2).all(|pair| pair[0] > pair[1]));
+            assert!(top.iter().all(|total| input.split("\n\n").nth(total.index)
+                == Some(total.calories.to_string().as_str())));
+        }
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day01, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        assert_eq!(
+            Day01::solve_stream(SAMPLE_INPUT.as_bytes()).unwrap(),
+            (24000, 45000)
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_trailing_blank_lines() {
+        let input = format!("{}\n\n", SAMPLE_INPUT);
+        let normalized = aoc::input::normalize(&input);
+        let parsed = Day01::parse(&normalized).unwrap();
+
+        assert_eq!(
+            Day01::solve_stream(input.as_bytes()).unwrap(),
+            (Day01::part1(&parsed), Day01::part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_invalid_calories() {
+        let error = Day01::solve_stream("1000\n\n2000\n2x00\n".as_bytes()).unwrap_err();
+
+        let StreamError::Input(error) = error else {
+            panic!("expected an input error, got {:?}", error);
+        };
+        assert_eq!(error.line, 4);
+        assert_eq!(error.text, "2x00");
+    }
+}
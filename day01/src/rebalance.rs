@@ -0,0 +1,351 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::Elf;
+
+/// How many assignments the exact search tries before settling for the best plan so far.
+const SEARCH_BUDGET: usize = 100_000;
+
+/// The most items the exact search takes on. It recurses once per item, so this bounds its stack
+/// depth.
+const SEARCH_MAX_ITEMS: usize = 1000;
+
+/// The items each elf would carry.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Plan {
+    pub loads: Vec<Vec<u64>>,
+}
+
+impl Plan {
+    /// What the heaviest elf would carry.
+    pub fn max_load(&self) -> u128 {
+        self.loads
+            .iter()
+            .map(|items| load(items))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn load(items: &[u64]) -> u128 {
+    items.iter().map(|&item| u128::from(item)).sum()
+}
+
+/// The best way found to share the items out again, next to how they are shared now.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rebalancing {
+    /// What the heaviest elf carries now.
+    pub current: u128,
+    pub plan: Plan,
+    /// No plan can have its heaviest elf carry less: the largest item, or the items spread
+    /// perfectly evenly.
+    pub lower_bound: u128,
+    /// Whether the search proved no plan does better than `plan`.
+    pub optimal: bool,
+}
+
+impl Rebalancing {
+    /// How much lighter the heaviest elf would be after rebalancing.
+    pub fn gap(&self) -> u128 {
+        self.current - self.plan.max_load()
+    }
+}
+
+impl fmt::Display for Rebalancing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "The heaviest elf carries {} calories", self.current)?;
+        write!(
+            f,
+            "Rebalanced, the heaviest would carry {} ({} less)",
+            self.plan.max_load(),
+            self.gap()
+        )?;
+
+        if self.optimal {
+            writeln!(f, ", which is optimal")
+        } else {
+            writeln!(f, ", and no plan carries less than {}", self.lower_bound)
+        }
+    }
+}
+
+/// Shares every elf's items out again over as many elves, so that the heaviest elf carries as
+/// little as possible. `None` when there are no elves to share between.
+///
+/// Starts from the longest processing time heuristic, then searches for better plans by branch
+/// and bound, which settles small inputs exactly.
+pub fn rebalance(elves: &[Elf]) -> Option<Rebalancing> {
+    let mut items = elves
+        .iter()
+        .flat_map(|elf| elf.items.iter().copied())
+        .collect::<Vec<_>>();
+    items.sort_unstable_by(|a, b| b.cmp(a));
+
+    let current = Plan {
+        loads: elves.iter().map(|elf| elf.items.clone()).collect(),
+    };
+    let heuristic = longest_processing_time(&items, elves.len())?;
+    let start = if heuristic.max_load() < current.max_load() {
+        heuristic
+    } else {
+        current.clone()
+    };
+
+    let lower_bound = lower_bound(&items, elves.len());
+    let (plan, exhausted) = if items.len() <= SEARCH_MAX_ITEMS {
+        branch_and_bound(&items, elves.len(), start, lower_bound)
+    } else {
+        (start, false)
+    };
+
+    Some(Rebalancing {
+        current: current.max_load(),
+        optimal: exhausted || plan.max_load() == lower_bound,
+        plan,
+        lower_bound,
+    })
+}
+
+/// Gives each item, largest first, to the elf carrying the least so far. `None` when there are no
+/// elves to give the items to.
+pub fn longest_processing_time(items: &[u64], elves: usize) -> Option<Plan> {
+    if elves == 0 {
+        return None;
+    }
+
+    let mut sorted = items.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut loads = vec![Vec::new(); elves];
+    let mut lightest = (0..elves)
+        .map(|elf| Reverse((0u128, elf)))
+        .collect::<BinaryHeap<_>>();
+
+    for item in sorted {
+        let Reverse((load, elf)) = lightest.pop().expect("there is at least one elf");
+        loads[elf].push(item);
+        lightest.push(Reverse((load + u128::from(item), elf)));
+    }
+
+    Some(Plan { loads })
+}
+
+fn lower_bound(items: &[u64], elves: usize) -> u128 {
+    let even = load(items).div_ceil(elves as u128);
+
+    even.max(items.iter().copied().max().map_or(0, u128::from))
+}
+
+/// The best plan that improves on `best`, if any, and whether the search got to try every
+/// assignment. `items` are sorted largest first, which finds good plans early.
+fn branch_and_bound(items: &[u64], elves: usize, best: Plan, lower_bound: u128) -> (Plan, bool) {
+    let mut remaining = vec![0; items.len() + 1];
+    for (idx, &item) in items.iter().enumerate().rev() {
+        remaining[idx] = remaining[idx + 1] + u128::from(item);
+    }
+
+    let mut search = Search {
+        items,
+        remaining,
+        loads: vec![0; elves],
+        assignment: Vec::with_capacity(items.len()),
+        best_max: best.max_load(),
+        best_assignment: None,
+        lower_bound,
+        budget: SEARCH_BUDGET,
+    };
+    let exhausted = search.assign(0);
+
+    let plan = match search.best_assignment {
+        Some(assignment) => {
+            let mut loads = vec![Vec::new(); elves];
+            for (&item, elf) in items.iter().zip(assignment) {
+                loads[elf].push(item);
+            }
+            Plan { loads }
+        }
+        None => best,
+    };
+
+    (plan, exhausted)
+}
+
+struct Search<'a> {
+    items: &'a [u64],
+    /// The total of `items[i..]` at `i`.
+    remaining: Vec<u128>,
+    loads: Vec<u128>,
+    assignment: Vec<usize>,
+    best_max: u128,
+    best_assignment: Option<Vec<usize>>,
+    lower_bound: u128,
+    budget: usize,
+}
+
+impl Search<'_> {
+    /// Tries every elf for item `next` onwards. Returns false if the budget ran out first.
+    fn assign(&mut self, next: usize) -> bool {
+        if self.best_max <= self.lower_bound {
+            return true;
+        }
+        let Some(&item) = self.items.get(next) else {
+            self.best_max = self.loads.iter().copied().max().unwrap_or(0);
+            self.best_assignment = Some(self.assignment.clone());
+            return true;
+        };
+
+        // Even shared out perfectly evenly, what is left would load some elf as much as the best
+        // plan does.
+        let total = self.loads.iter().sum::<u128>() + self.remaining[next];
+        if total.div_ceil(self.loads.len() as u128) >= self.best_max {
+            return true;
+        }
+
+        for elf in 0..self.loads.len() {
+            let load = self.loads[elf];
+            // Elves carrying the same load lead to the same plans, so only the first is tried.
+            if load + u128::from(item) >= self.best_max || self.loads[..elf].contains(&load) {
+                continue;
+            }
+            if self.budget == 0 {
+                return false;
+            }
+            self.budget -= 1;
+
+            self.loads[elf] += u128::from(item);
+            self.assignment.push(elf);
+            let finished = self.assign(next + 1);
+            self.assignment.pop();
+            self.loads[elf] -= u128::from(item);
+
+            if !finished {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc::Rng;
+
+    use super::*;
+    use crate::SAMPLE_INPUT;
+
+    fn elves(items: &[&[u64]]) -> Vec<Elf> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, items)| Elf {
+                index,
+                items: items.to_vec(),
+            })
+            .collect()
+    }
+
+    fn sorted_items(plan: &Plan) -> Vec<u64> {
+        let mut items = plan.loads.concat();
+        items.sort_unstable();
+        items
+    }
+
+    /// The lightest heaviest elf over every way of sharing out the items.
+    fn brute_force(items: &[u64], elves: usize) -> u128 {
+        (0..elves.pow(items.len() as u32))
+            .map(|mut code| {
+                let mut loads = vec![0u128; elves];
+                for &item in items {
+                    loads[code % elves] += u128::from(item);
+                    code /= elves;
+                }
+                loads.into_iter().max().unwrap()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rebalance() {
+        let rebalancing = rebalance(&Elf::parse_all(SAMPLE_INPUT).unwrap()).unwrap();
+
+        assert_eq!(rebalancing.current, 24000);
+        assert_eq!(rebalancing.plan.max_load(), 11000);
+        assert_eq!(rebalancing.plan.loads.len(), 5);
+        assert_eq!(rebalancing.gap(), 13000);
+        assert!(rebalancing.optimal);
+        assert_eq!(
+            rebalancing.to_string(),
+            "The heaviest elf carries 24000 calories\n\
+             Rebalanced, the heaviest would carry 11000 (13000 less), which is optimal\n"
+        );
+
+        assert_eq!(rebalance(&[]), None);
+    }
+
+    #[test]
+    fn test_beats_longest_processing_time() {
+        let elves = elves(&[&[3, 3], &[2, 2, 2]]);
+        let items = [3, 3, 2, 2, 2];
+
+        assert_eq!(longest_processing_time(&items, 2).unwrap().max_load(), 7);
+        assert_eq!(longest_processing_time(&items, 0), None);
+        assert_eq!(longest_processing_time(&[], 0), None);
+
+        let rebalancing = rebalance(&elves).unwrap();
+        assert_eq!(rebalancing.plan.max_load(), 6);
+        assert_eq!(rebalancing.gap(), 0);
+        assert!(rebalancing.optimal);
+    }
+
+    #[test]
+    fn test_rebalance_matches_brute_force() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..200 {
+            let elves = (0..rng.range(1..=3))
+                .map(|index| Elf {
+                    index: index as usize,
+                    items: (0..rng.range(0..=3)).map(|_| rng.range(1..=20)).collect(),
+                })
+                .collect::<Vec<_>>();
+            let items = elves
+                .iter()
+                .flat_map(|elf| elf.items.clone())
+                .collect::<Vec<_>>();
+
+            let rebalancing = rebalance(&elves).unwrap();
+            let best = rebalancing.plan.max_load();
+
+            assert!(rebalancing.optimal);
+            assert_eq!(best, brute_force(&items, elves.len()));
+            assert!(rebalancing.lower_bound <= best && best <= rebalancing.current);
+            assert_eq!(rebalancing.plan.loads.len(), elves.len());
+            assert_eq!(
+                sorted_items(&rebalancing.plan),
+                sorted_items(&Plan { loads: vec![items] })
+            );
+        }
+    }
+
+    #[test]
+    fn test_rebalance_large() {
+        let mut rng = Rng::new(0);
+        let elves = (0..200)
+            .map(|index| Elf {
+                index,
+                items: (0..rng.range(1..=15))
+                    .map(|_| rng.range(1000..=60000))
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let rebalancing = rebalance(&elves).unwrap();
+        let best = rebalancing.plan.max_load();
+
+        assert!(rebalancing.lower_bound <= best && best < rebalancing.current);
+        assert_eq!(rebalancing.plan.loads.len(), 200);
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use aoc::InputError;
+
+use crate::{Elf, ParseError};
+
+/// The percentiles of the elves' totals a report lists. The 50th is the median, listed apart.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+const HISTOGRAM_BUCKETS: u64 = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// How the calories are spread over the elves.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalorieStats {
+    pub elves: usize,
+    pub total: u128,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    /// Each of [`PERCENTILES`] with the total at that percentile, by nearest rank.
+    pub percentiles: Vec<(u8, u64)>,
+    /// How many elves carry each number of items.
+    pub item_counts: BTreeMap<usize, usize>,
+    /// The elves' totals in up to ten buckets of equal width, from `min` up.
+    pub histogram: Vec<Bucket>,
+}
+
+/// The elves whose totals are between `start` and `end`, both included.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub elves: usize,
+}
+
+/// The statistics of every elf in `input`.
+pub fn calorie_stats(input: &str) -> Result<CalorieStats, InputError<ParseError>> {
+    let elves = Elf::parse_all(input)?;
+
+    Ok(CalorieStats::from_elves(&elves).expect("an input always has an elf"))
+}
+
+impl CalorieStats {
+    /// `None` when there are no elves to say anything about.
+    pub fn from_elves(elves: &[Elf]) -> Option<CalorieStats> {
+        let mut totals = elves.iter().map(Elf::total_calories).collect::<Vec<_>>();
+        totals.sort_unstable();
+        let (&min, &max) = (totals.first()?, totals.last()?);
+
+        let n = totals.len();
+        let total = totals.iter().map(|&total| total as u128).sum::<u128>();
+        let mean = total as f64 / n as f64;
+        let median = if n % 2 == 1 {
+            totals[n / 2] as f64
+        } else {
+            (totals[n / 2 - 1] as f64 + totals[n / 2] as f64) / 2.0
+        };
+        let variance = totals
+            .iter()
+            .map(|&total| (total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| {
+                let rank = (p as usize * n).div_ceil(100).max(1);
+                (p, totals[rank - 1])
+            })
+            .collect();
+
+        let mut item_counts = BTreeMap::new();
+        for elf in elves {
+            *item_counts.entry(elf.items.len()).or_insert(0) += 1;
+        }
+
+        Some(CalorieStats {
+            elves: n,
+            total,
+            min,
+            max,
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            percentiles,
+            item_counts,
+            histogram: histogram(&totals, min, max),
+        })
+    }
+}
+
+fn histogram(totals: &[u64], min: u64, max: u64) -> Vec<Bucket> {
+    let width = (max - min) / HISTOGRAM_BUCKETS + 1;
+    let mut histogram = (0..=(max - min) / width)
+        .map(|i| {
+            let start = min + i * width;
+
+            Bucket {
+                start,
+                end: start.saturating_add(width - 1).min(max),
+                elves: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for &total in totals {
+        histogram[((total - min) / width) as usize].elves += 1;
+    }
+
+    histogram
+}
+
+impl fmt::Display for CalorieStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elves:        {}", self.elves)?;
+        writeln!(f, "Total:        {} calories", self.total)?;
+        writeln!(f, "Range:        {} to {}", self.min, self.max)?;
+        writeln!(f, "Mean:         {:.1}", self.mean)?;
+        writeln!(f, "Median:       {:.1}", self.median)?;
+        writeln!(f, "Std dev:      {:.1}", self.std_dev)?;
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, total)| format!("p{} {}", p, total))
+            .collect::<Vec<_>>();
+        writeln!(f, "Percentiles:  {}", percentiles.join(", "))?;
+
+        writeln!(f, "\nElves by items carried:")?;
+        for (items, elves) in &self.item_counts {
+            writeln!(f, "  {:>4} {:<6} {}", items, plural(*items, "item"), elves)?;
+        }
+
+        writeln!(f, "\nCalories per elf:")?;
+        let most = self.histogram.iter().map(|bucket| bucket.elves).max();
+        let digits = self.max.to_string().len();
+        for bucket in &self.histogram {
+            let bar = (bucket.elves * HISTOGRAM_WIDTH).div_ceil(most.unwrap_or(1));
+            let line = format!(
+                "  {:>digits$} - {:>digits$} {:>5} {}",
+                bucket.start,
+                bucket.end,
+                bucket.elves,
+                "#".repeat(bar)
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        noun.to_owned()
+    } else {
+        format!("{}s", noun)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SAMPLE_INPUT;
+
+    #[test]
+    fn test_calorie_stats() {
+        let stats = calorie_stats(SAMPLE_INPUT).unwrap();
+
+        assert_eq!((stats.elves, stats.total), (5, 55000));
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(stats.std_dev, 48_800_000f64.sqrt());
+        assert_eq!(
+            stats.percentiles,
+            [
+                (10, 4000),
+                (25, 6000),
+                (75, 11000),
+                (90, 24000),
+                (99, 24000)
+            ]
+        );
+        assert_eq!(stats.item_counts, BTreeMap::from([(1, 2), (2, 1), (3, 2)]));
+
+        assert_eq!(stats.histogram.len(), 10);
+        assert_eq!(
+            stats.histogram[0],
+            Bucket {
+                start: 4000,
+                end: 6000,
+                elves: 2
+            }
+        );
+        assert_eq!(stats.histogram[9].end, 24000);
+        assert_eq!(stats.histogram[9].elves, 1);
+        assert_eq!(
+            stats
+                .histogram
+                .iter()
+                .map(|bucket| bucket.elves)
+                .sum::<usize>(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_calorie_stats_even_and_equal() {
+        let stats = calorie_stats("1\n\n4\n\n2\n\n3").unwrap();
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.histogram.len(), 4);
+
+        let stats = calorie_stats("7\n\n7").unwrap();
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(
+            stats.histogram,
+            [Bucket {
+                start: 7,
+                end: 7,
+                elves: 2
+            }]
+        );
+
+        assert_eq!(CalorieStats::from_elves(&[]), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let report = calorie_stats(SAMPLE_INPUT).unwrap().to_string();
+
+        assert!(report.contains("Median:       10000.0\n"));
+        assert!(report.contains("p10 4000, p25 6000"));
+        assert!(report.contains("     1 item   2\n"));
+        assert!(report.contains("     3 items  2\n"));
+        assert!(report.contains("   4000 -  6000     2 ########################################\n"));
+        assert!(report.contains("  22009 - 24000     1 ####################\n"));
+        assert!(report.contains("   6001 -  8001     0\n"));
+    }
+}
@@ -0,0 +1,36 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::Day02;
+
+impl Generate for Day02 {
+    /// `size` is the number of rounds in the strategy guide.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<usize, usize> {
+        let mut input = String::with_capacity(size * 4);
+        let (mut score_as_shapes, mut score) = (0, 0);
+
+        for _ in 0..size {
+            // Rock, paper and scissors are 0, 1 and 2, so that each beats the one before it.
+            let opponent = rng.below(3);
+            let column = rng.below(3);
+
+            // Read as a shape, the column is ours; we win if it is the one after theirs.
+            let result = (column + 3 - opponent) % 3;
+            score_as_shapes += column + 1 + [3, 6, 0][result];
+
+            // Read as an outcome, the column is lose, draw or win.
+            let shape = (opponent + [2, 0, 1][column]) % 3;
+            score += shape + 1 + [0, 3, 6][column];
+
+            input.push(char::from(b'A' + opponent as u8));
+            input.push(' ');
+            input.push(char::from(b'X' + column as u8));
+            input.push('\n');
+        }
+
+        Generated {
+            input,
+            answer1: score_as_shapes,
+            answer2: score,
+        }
+    }
+}
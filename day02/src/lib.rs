@@ -0,0 +1,442 @@
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc::differential::Implementation;
+use aoc::{InputError, LineReader, Part, Solution, Spanned, StreamError, Streaming};
+
+mod generate;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    WrongTokenCount { expected: usize, found: usize },
+    UnknownShape(String),
+    UnknownOutcome(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::WrongTokenCount { expected, found } => {
+                write!(
+                    f,
+                    "expected {} tokens in a round, found {}",
+                    expected, found
+                )
+            }
+            ParseError::UnknownShape(s) => write!(f, "unknown shape {:?}", s),
+            ParseError::UnknownOutcome(s) => write!(f, "unknown round outcome {:?}", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum RoundOutcome {
+    Loss = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl RoundOutcome {
+    fn score(&self) -> usize {
+        *self as usize
+    }
+
+    /// The shape this column stands for when the strategy guide is read as shapes to play.
+    fn as_shape(&self) -> Shape {
+        match self {
+            RoundOutcome::Loss => Shape::Rock,
+            RoundOutcome::Draw => Shape::Paper,
+            RoundOutcome::Win => Shape::Scissors,
+        }
+    }
+}
+
+impl TryFrom<char> for RoundOutcome {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'X' => Ok(Self::Loss),
+            'Y' => Ok(Self::Draw),
+            'Z' => Ok(Self::Win),
+            _ => Err(ParseError::UnknownOutcome(c.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RoundOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            RoundOutcome::Loss => 'X',
+            RoundOutcome::Draw => 'Y',
+            RoundOutcome::Win => 'Z',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Shape {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl Shape {
+    fn score(&self) -> usize {
+        *self as usize
+    }
+
+    fn play_for_desired_outcome(&self, desired_outcome: RoundOutcome) -> Self {
+        use RoundOutcome::{Draw, Loss, Win};
+        use Shape::{Paper, Rock, Scissors};
+
+        match (self, desired_outcome) {
+            (Rock, Loss) => Scissors,
+            (Rock, Draw) => Rock,
+            (Rock, Win) => Paper,
+            (Paper, Loss) => Rock,
+            (Paper, Draw) => Paper,
+            (Paper, Win) => Scissors,
+            (Scissors, Loss) => Paper,
+            (Scissors, Draw) => Scissors,
+            (Scissors, Win) => Rock,
+        }
+    }
+
+    fn outcome_against(&self, opponent: Shape) -> RoundOutcome {
+        use RoundOutcome::{Draw, Loss, Win};
+        use Shape::{Paper, Rock, Scissors};
+
+        match (self, opponent) {
+            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => Win,
+            (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => Loss,
+            _ => Draw,
+        }
+    }
+}
+
+impl TryFrom<char> for Shape {
+    type Error = ParseError;
+
+    fn try_from(x: char) -> Result<Self, Self::Error> {
+        match x {
+            'A' => Ok(Shape::Rock),
+            'B' => Ok(Shape::Paper),
+            'C' => Ok(Shape::Scissors),
+            _ => Err(ParseError::UnknownShape(x.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Shape::Rock => 'A',
+            Shape::Paper => 'B',
+            Shape::Scissors => 'C',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Round(Shape, RoundOutcome);
+
+impl FromStr for Round {
+    type Err = Spanned<ParseError>;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let tokens = line.split_ascii_whitespace().collect::<Vec<_>>();
+        let [shape, outcome]: [&str; 2] = tokens.try_into().map_err(|tokens: Vec<_>| {
+            let kind = ParseError::WrongTokenCount {
+                expected: 2,
+                found: tokens.len(),
+            };
+
+            Spanned::at(kind, line, line)
+        })?;
+
+        let shape = single_char(shape)
+            .ok_or_else(|| ParseError::UnknownShape(shape.to_owned()))
+            .and_then(Shape::try_from)
+            .map_err(|kind| Spanned::at(kind, line, shape))?;
+        let outcome = single_char(outcome)
+            .ok_or_else(|| ParseError::UnknownOutcome(outcome.to_owned()))
+            .and_then(RoundOutcome::try_from)
+            .map_err(|kind| Spanned::at(kind, line, outcome))?;
+
+        Ok(Round(shape, outcome))
+    }
+}
+
+/// Writes a round the way the strategy guide does, which parses back to the same round.
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
+fn single_char(token: &str) -> Option<char> {
+    let mut chars = token.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+impl Round {
+    fn score(&self) -> usize {
+        let my_shape = self.0.play_for_desired_outcome(self.1);
+        my_shape.score() + self.1.score()
+    }
+
+    fn score_as_shapes(&self) -> usize {
+        let my_shape = self.1.as_shape();
+        my_shape.score() + my_shape.outcome_against(self.0).score()
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Vec<Round>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = InputError<ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        parse_input(input).map_err(|e| e.locate(input))
+    }
+
+    fn part1(rounds: &Self::Parsed<'_>) -> Self::Answer1 {
+        total_score_as_shapes(rounds)
+    }
+
+    fn part2(rounds: &Self::Parsed<'_>) -> Self::Answer2 {
+        total_score(rounds)
+    }
+}
+
+impl Streaming for Day02 {
+    type ParseError = ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseError>> {
+        let mut lines = LineReader::new(reader);
+        let (mut score_as_shapes, mut score) = (0, 0);
+
+        while let Some(line) = lines.next_line()? {
+            let round = line.parse::<Round>().map_err(|e| lines.locate(e))?;
+
+            score_as_shapes += round.score_as_shapes();
+            score += round.score();
+        }
+
+        Ok((score_as_shapes, score))
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day02>(Part::One),
+    Implementation::solution::<Day02>(Part::Two),
+    Implementation::streaming::<Day02>(Part::One),
+    Implementation::streaming::<Day02>(Part::Two),
+];
+
+fn parse_input(input: &str) -> Result<Vec<Round>, Spanned<ParseError>> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: Spanned<_>| e.within(input, line)))
+        .collect()
+}
+
+fn total_score_as_shapes(rounds: &[Round]) -> usize {
+    rounds.iter().map(Round::score_as_shapes).sum()
+}
+
+fn total_score(rounds: &[Round]) -> usize {
+    rounds.iter().map(Round::score).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::RoundOutcome::{Draw, Loss, Win};
+    use crate::Shape::{Paper, Rock, Scissors};
+    use aoc::differential::cross_check;
+    use aoc::{Rng, Solution, Spanned, StreamError, Streaming};
+
+    use crate::{
+        parse_input, total_score, total_score_as_shapes, Day02, ParseError, Round, RoundOutcome,
+        Shape, IMPLEMENTATIONS,
+    };
+
+    const SHAPES: [Shape; 3] = [Rock, Paper, Scissors];
+    const OUTCOMES: [RoundOutcome; 3] = [Loss, Draw, Win];
+
+    fn random_round(rng: &mut Rng) -> Round {
+        Round(*rng.choose(&SHAPES), *rng.choose(&OUTCOMES))
+    }
+
+    /// At least `min` spaces and tabs.
+    fn random_whitespace(rng: &mut Rng, min: u64) -> String {
+        (0..rng.range(min..=3))
+            .map(|_| *rng.choose(&[' ', '\t']))
+            .collect()
+    }
+
+    static SAMPLE_INPUT: &str = r#"A Y
+B X
+C Z"#;
+
+    #[test]
+    fn test_input_parsing() {
+        let rounds: Vec<Round> = parse_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(
+            rounds,
+            vec![Round(Rock, Draw), Round(Paper, Loss), Round(Scissors, Win)]
+        );
+    }
+
+    #[test]
+    fn test_round_display_round_trips() {
+        for shape in SHAPES {
+            for outcome in OUTCOMES {
+                let round = Round(shape, outcome);
+
+                assert_eq!(round.to_string().parse(), Ok(round));
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_display_is_canonical() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let round = random_round(&mut rng);
+            let line = format!(
+                "{}{}{}{}{}",
+                random_whitespace(&mut rng, 0),
+                round.0,
+                random_whitespace(&mut rng, 1),
+                round.1,
+                random_whitespace(&mut rng, 0)
+            );
+
+            let parsed = line.parse::<Round>().unwrap();
+            assert_eq!(parsed, round);
+            assert_eq!(parsed.to_string(), format!("{} {}", round.0, round.1));
+        }
+    }
+
+    #[test]
+    fn test_score_bounds() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..100 {
+            let rounds = (0..rng.range(0..=50))
+                .map(|_| random_round(&mut rng))
+                .collect::<Vec<_>>();
+
+            for round in &rounds {
+                // Whatever the outcome, some shape has been played for it.
+                assert!((1..=3).contains(&(round.score() - round.1.score())));
+                assert!((1..=9).contains(&round.score_as_shapes()));
+            }
+            for total in [total_score(&rounds), total_score_as_shapes(&rounds)] {
+                assert!((rounds.len()..=9 * rounds.len()).contains(&total));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_rounds() {
+        assert_eq!(
+            "A".parse::<Round>(),
+            Err(Spanned {
+                kind: ParseError::WrongTokenCount {
+                    expected: 2,
+                    found: 1
+                },
+                span: 0..1
+            })
+        );
+        assert_eq!(
+            "D X".parse::<Round>(),
+            Err(Spanned {
+                kind: ParseError::UnknownShape(String::from("D")),
+                span: 0..1
+            })
+        );
+        assert_eq!(
+            "A XY".parse::<Round>(),
+            Err(Spanned {
+                kind: ParseError::UnknownOutcome(String::from("XY")),
+                span: 2..4
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_location() {
+        let error = Day02::parse("A Y\nB Q\nC Z").unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 2..3);
+        assert_eq!(error.text, "B Q");
+    }
+
+    #[test]
+    fn test_total_score_as_shapes() {
+        assert_eq!(
+            total_score_as_shapes(&parse_input(SAMPLE_INPUT).unwrap()),
+            15
+        );
+    }
+
+    #[test]
+    fn test_total_score() {
+        assert_eq!(total_score(&parse_input(SAMPLE_INPUT).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        assert_eq!(
+            Day02::solve_stream(SAMPLE_INPUT.as_bytes()).unwrap(),
+            (15, 12)
+        );
+
+        let Err(StreamError::Input(error)) = Day02::solve_stream("A Y\nB Q\n".as_bytes()) else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 2..3);
+    }
+
+    #[test]
+    fn test_solve_stream_trailing_blank_lines() {
+        let input = format!("{}\n\n", SAMPLE_INPUT);
+        let normalized = aoc::input::normalize(&input);
+        let parsed = Day02::parse(&normalized).unwrap();
+
+        assert_eq!(
+            Day02::solve_stream(input.as_bytes()).unwrap(),
+            (Day02::part1(&parsed), Day02::part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day02, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+}
@@ -0,0 +1,73 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::{priority_for_item_type, Day03};
+
+static ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Generate for Day03 {
+    /// `size` is the number of rucksacks, rounded up to whole groups of three.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<usize, usize> {
+        let mut input = String::new();
+        let (mut priorities, mut badge_priorities) = (0, 0);
+
+        for _ in 0..size.div_ceil(3) {
+            let mut item_types = ITEM_TYPES.chars().collect::<Vec<_>>();
+            rng.shuffle(&mut item_types);
+
+            // The badge is the only item type all three carry: everything else is handed out so
+            // that no two rucksacks in the group share it.
+            let (&badge, rest) = item_types.split_first().unwrap();
+            for pool in rest.chunks(rest.len() / 3) {
+                let (line, shared) = rucksack(rng, badge, pool);
+                input.push_str(&line);
+                input.push('\n');
+                priorities += priority_for_item_type(shared);
+            }
+
+            badge_priorities += priority_for_item_type(badge);
+        }
+
+        Generated {
+            input,
+            answer1: priorities,
+            answer2: badge_priorities,
+        }
+    }
+}
+
+/// A rucksack holding `badge` and item types from `pool`, and the one item type that is in both
+/// of its compartments.
+fn rucksack(rng: &mut Rng, badge: char, pool: &[char]) -> (String, char) {
+    let shared = if rng.chance(1, 10) {
+        badge
+    } else {
+        *rng.choose(pool)
+    };
+
+    let others = pool
+        .iter()
+        .copied()
+        .filter(|&item_type| item_type != shared)
+        .collect::<Vec<_>>();
+    let (left_only, right_only) = others.split_at(others.len() / 2);
+
+    let mut left = vec![shared];
+    let mut right = vec![shared];
+    if shared != badge {
+        if rng.chance(1, 2) {
+            left.push(badge);
+        } else {
+            right.push(badge);
+        }
+    }
+
+    let len = rng.range(2..=16) as usize;
+    for (compartment, only) in [(&mut left, left_only), (&mut right, right_only)] {
+        while compartment.len() < len {
+            compartment.push(*rng.choose(only));
+        }
+        rng.shuffle(compartment);
+    }
+
+    (left.into_iter().chain(right).collect(), shared)
+}
@@ -0,0 +1,434 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::mem;
+
+use aoc::differential::Implementation;
+use aoc::{InputError, LineReader, Part, Solution, Spanned, StreamError, Streaming};
+
+mod generate;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    OddItemCount(usize),
+    InvalidItem(char),
+    /// The two compartments of a rucksack have no item type in common.
+    NoSharedItem,
+    /// The three rucksacks of a group have no item type in common.
+    NoBadge,
+    /// The last group has this many rucksacks rather than three.
+    IncompleteGroup(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::OddItemCount(n) => write!(
+                f,
+                "a rucksack must split into two equal compartments, found {} items",
+                n
+            ),
+            ParseError::InvalidItem(c) => {
+                write!(f, "invalid item {:?}, expected an ASCII letter", c)
+            }
+            ParseError::NoSharedItem => {
+                write!(f, "the compartments of a rucksack have no item in common")
+            }
+            ParseError::NoBadge => write!(f, "the rucksacks of a group have no item in common"),
+            ParseError::IncompleteGroup(n) => write!(
+                f,
+                "the last group has {} rucksacks, expected groups of 3",
+                n
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rucksack<'a>(&'a str, &'a str);
+
+impl<'a> TryFrom<&'a str> for Rucksack<'a> {
+    type Error = Spanned<ParseError>;
+
+    fn try_from(line: &'a str) -> Result<Self, Self::Error> {
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(Spanned {
+                kind: ParseError::InvalidItem(c),
+                span: idx..idx + c.len_utf8(),
+            });
+        }
+
+        let len = line.len();
+        if !len.is_multiple_of(2) {
+            return Err(Spanned::at(ParseError::OddItemCount(len), line, line));
+        }
+
+        let rucksack = Rucksack(&line[0..len / 2], &line[len / 2..]);
+        if rucksack.shared_item().is_none() {
+            return Err(Spanned::at(ParseError::NoSharedItem, line, line));
+        }
+
+        Ok(rucksack)
+    }
+}
+
+/// Writes both compartments back as the one line they were split from.
+impl fmt::Display for Rucksack<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+impl<'a> Rucksack<'a> {
+    fn find_duplicate_item(&self) -> char {
+        self.shared_item()
+            .expect("parsing checks the compartments share an item")
+    }
+
+    fn shared_item(&self) -> Option<char> {
+        let Rucksack(left, right) = *self;
+
+        left.chars().find(|&l| right.contains(l))
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Vec<Rucksack<'a>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = InputError<ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        parse_rucksacks(input).map_err(|e| e.locate(input))
+    }
+
+    fn part1(rucksacks: &Self::Parsed<'_>) -> Self::Answer1 {
+        sum_of_priorities(rucksacks)
+    }
+
+    fn part2(rucksacks: &Self::Parsed<'_>) -> Self::Answer2 {
+        sum_of_priorities_for_badges(rucksacks)
+    }
+}
+
+impl Streaming for Day03 {
+    type ParseError = ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseError>> {
+        let mut lines = LineReader::new(reader);
+        let (mut priorities, mut badge_priorities) = (0, 0);
+        let mut group = Vec::with_capacity(3);
+        let mut last_line = String::new();
+
+        while let Some(line) = lines.next_line()? {
+            let duplicate = Rucksack::try_from(line).map(|rucksack| rucksack.find_duplicate_item());
+            let items = line.chars().collect::<HashSet<_>>();
+            // The whole line, for an error about the group it ends.
+            let line_span = 0..line.len();
+            last_line.clear();
+            last_line.push_str(line);
+            priorities += priority_for_item_type(duplicate.map_err(|e| lines.locate(e))?);
+
+            group.push(items);
+            if group.len() == 3 {
+                let badge = find_badge(mem::take(&mut group)).map_err(|kind| {
+                    lines.locate(Spanned {
+                        kind,
+                        span: line_span,
+                    })
+                })?;
+                badge_priorities += priority_for_item_type(badge);
+            }
+        }
+
+        if !group.is_empty() {
+            return Err(StreamError::Input(InputError {
+                kind: ParseError::IncompleteGroup(group.len()),
+                line: lines.number(),
+                columns: 0..last_line.len(),
+                text: last_line,
+            }));
+        }
+
+        Ok((priorities, badge_priorities))
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day03>(Part::One),
+    Implementation::solution::<Day03>(Part::Two),
+    Implementation::streaming::<Day03>(Part::One),
+    Implementation::streaming::<Day03>(Part::Two),
+];
+
+/// Parses every rucksack, checking that they make up whole groups of three with a badge each.
+fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack<'_>>, Spanned<ParseError>> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut rucksacks = Vec::with_capacity(lines.len());
+
+    for group in lines.chunks(3) {
+        for &line in group {
+            rucksacks.push(Rucksack::try_from(line).map_err(|e| e.within(input, line))?);
+        }
+
+        let sets = group.iter().map(|line| line.chars().collect()).collect();
+        let last = group[group.len() - 1];
+        find_badge(sets).map_err(|kind| Spanned::at(kind, input, last))?;
+    }
+
+    Ok(rucksacks)
+}
+
+fn priority_for_item_type(item_type: char) -> usize {
+    let diff = if item_type.is_ascii_lowercase() {
+        96
+    } else {
+        38
+    };
+
+    (item_type as usize) - diff
+}
+
+fn sum_of_priorities(rucksacks: &[Rucksack]) -> usize {
+    rucksacks
+        .iter()
+        .map(Rucksack::find_duplicate_item)
+        .map(priority_for_item_type)
+        .sum()
+}
+
+fn sum_of_priorities_for_badges(rucksacks: &[Rucksack]) -> usize {
+    rucksacks
+        .iter()
+        .map(|&Rucksack(l, r)| l.to_owned() + r)
+        .collect::<Vec<_>>()
+        .chunks(3)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|s| s.chars().collect::<HashSet<_>>())
+                .collect::<Vec<_>>() // Once `array_chunks` is stabilized this can be collected without allocating, I think
+        })
+        .map(|sets| find_badge(sets).expect("parsing checks every group has a badge"))
+        .map(priority_for_item_type)
+        .sum()
+}
+
+/// The item type carried by every rucksack of a group of three.
+fn find_badge(sets: Vec<HashSet<char>>) -> Result<char, ParseError> {
+    if sets.len() != 3 {
+        return Err(ParseError::IncompleteGroup(sets.len()));
+    }
+
+    find_only_intersection(sets).ok_or(ParseError::NoBadge)
+}
+
+fn find_only_intersection(sets: Vec<HashSet<char>>) -> Option<char> {
+    let mut first_set = sets[0].clone();
+
+    first_set.retain(|e| sets.iter().all(|set| set.contains(e)));
+
+    first_set.iter().last().copied()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use aoc::differential::cross_check;
+    use aoc::{Rng, Solution, Spanned, StreamError, Streaming};
+
+    use crate::{
+        find_badge, find_only_intersection, parse_rucksacks, priority_for_item_type,
+        sum_of_priorities, sum_of_priorities_for_badges, Day03, ParseError, Rucksack,
+        IMPLEMENTATIONS,
+    };
+
+    static ITEM_TYPES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    static SAMPLE_INPUT: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"#;
+
+    #[test]
+    fn test_rucksack_display_round_trips() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let mut line = (0..2 * rng.range(1..=24))
+                .map(|_| char::from(*rng.choose(ITEM_TYPES)))
+                .collect::<String>();
+            // Both compartments need an item type in common.
+            let shared = line.chars().next().unwrap();
+            line.pop();
+            line.push(shared);
+            let rucksack = Rucksack::try_from(line.as_str()).unwrap();
+
+            assert_eq!(rucksack.0.len(), rucksack.1.len());
+            assert_eq!(rucksack.to_string(), line);
+        }
+    }
+
+    #[test]
+    fn test_priorities_are_distinct() {
+        let mut priorities = ITEM_TYPES
+            .iter()
+            .map(|&item_type| priority_for_item_type(char::from(item_type)))
+            .collect::<Vec<_>>();
+        priorities.sort();
+
+        assert_eq!(priorities, (1..=52).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parse_rucksacks() {
+        assert_eq!(
+            parse_rucksacks(SAMPLE_INPUT).unwrap(),
+            vec![
+                Rucksack("vJrwpWtwJgWr", "hcsFMMfFFhFp"),
+                Rucksack("jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"),
+                Rucksack("PmmdzqPrV", "vPwwTWBwg"),
+                Rucksack("wMqvLMZHhHMvwLH", "jbvcjnnSBnvTQFn"),
+                Rucksack("ttgJtRGJ", "QctTZtZT"),
+                Rucksack("CrZsJsPPZsGz", "wwsLwLmpwMDw"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        assert_eq!(
+            Rucksack::try_from("vJrwpWtwJgWrhcsFMMfFFhF"),
+            Err(Spanned {
+                kind: ParseError::OddItemCount(23),
+                span: 0..23
+            })
+        );
+        assert_eq!(
+            Rucksack::try_from("vJrwpWtwJg rhcsFMMfFFhFp"),
+            Err(Spanned {
+                kind: ParseError::InvalidItem(' '),
+                span: 10..11
+            })
+        );
+
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrF-sSL";
+        let error = parse_rucksacks(input).unwrap_err().locate(input);
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 28..29);
+
+        assert_eq!(
+            Rucksack::try_from("ab").map_err(|e| e.kind),
+            Err(ParseError::NoSharedItem)
+        );
+        assert_eq!(
+            Rucksack::try_from("").map_err(|e| e.kind),
+            Err(ParseError::NoSharedItem)
+        );
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        let error = Day03::parse("aa\nbb\ncc").unwrap_err();
+        assert_eq!(error.kind, ParseError::NoBadge);
+        assert_eq!((error.line, error.text.as_str()), (3, "cc"));
+
+        let error = Day03::parse("aa").unwrap_err();
+        assert_eq!(error.kind, ParseError::IncompleteGroup(1));
+        assert_eq!(error.line, 1);
+
+        for input in ["aa\nbb\ncc\n", "aa\n"] {
+            let Err(StreamError::Input(stream_error)) = Day03::solve_stream(input.as_bytes())
+            else {
+                panic!("expected an input error");
+            };
+            let error = Day03::parse(input).unwrap_err();
+            assert_eq!(stream_error, error);
+        }
+    }
+
+    #[test]
+    fn test_find_duplicate_items() {
+        let rucksacks = parse_rucksacks(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            rucksacks
+                .iter()
+                .map(Rucksack::find_duplicate_item)
+                .collect::<Vec<char>>(),
+            vec!['p', 'L', 'P', 'v', 't', 's']
+        );
+    }
+
+    #[test]
+    fn test_sum_of_priorities() {
+        let rucksacks = parse_rucksacks(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(sum_of_priorities(&rucksacks), 157);
+    }
+
+    #[test]
+    fn test_find_only_intersection() {
+        let sets = SAMPLE_INPUT
+            .lines()
+            .map(|line| line.chars().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(find_only_intersection(sets[0..3].to_vec()), Some('r'));
+        assert_eq!(find_only_intersection(sets[3..].to_vec()), Some('Z'));
+        assert_eq!(
+            find_badge(sets[0..2].to_vec()),
+            Err(ParseError::IncompleteGroup(2))
+        );
+    }
+
+    #[test]
+    fn test_sum_of_priorities_for_badges() {
+        let rucksacks = parse_rucksacks(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(sum_of_priorities_for_badges(&rucksacks), 70);
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        assert_eq!(
+            Day03::solve_stream(SAMPLE_INPUT.as_bytes()).unwrap(),
+            (157, 70)
+        );
+
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrF-sSL\n";
+        let Err(StreamError::Input(error)) = Day03::solve_stream(input.as_bytes()) else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 28..29);
+    }
+
+    #[test]
+    fn test_solve_stream_trailing_blank_lines() {
+        let input = format!("{}\n\n", SAMPLE_INPUT);
+        let normalized = aoc::input::normalize(&input);
+        let parsed = Day03::parse(&normalized).unwrap();
+
+        assert_eq!(
+            Day03::solve_stream(input.as_bytes()).unwrap(),
+            (Day03::part1(&parsed), Day03::part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day03, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+}
@@ -0,0 +1,34 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::Day04;
+
+impl Generate for Day04 {
+    /// `size` is the number of pairs of elves.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<usize, usize> {
+        let mut input = String::new();
+        let (mut fully_overlapping, mut any_overlapping) = (0, 0);
+
+        for _ in 0..size {
+            let [(start1, end1), (start2, end2)] = [sections(rng), sections(rng)];
+
+            fully_overlapping += usize::from(
+                (start1 <= start2 && end2 <= end1) || (start2 <= start1 && end1 <= end2),
+            );
+            any_overlapping += usize::from(start1 <= end2 && start2 <= end1);
+
+            input.push_str(&format!("{}-{},{}-{}\n", start1, end1, start2, end2));
+        }
+
+        Generated {
+            input,
+            answer1: fully_overlapping,
+            answer2: any_overlapping,
+        }
+    }
+}
+
+fn sections(rng: &mut Rng) -> (u64, u64) {
+    let start = rng.range(1..=99);
+
+    (start, rng.range(start..=99))
+}
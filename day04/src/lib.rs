@@ -0,0 +1,515 @@
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::num::ParseIntError;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc::differential::Implementation;
+use aoc::{Answer, InputError, LineReader, Part, Solution, Spanned, StreamError, Streaming};
+
+mod generate;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    WrongTokenCount {
+        expected: usize,
+        found: usize,
+    },
+    InvalidSection {
+        section: String,
+        source: ParseIntError,
+    },
+    /// A range like `5-3`, which would hold no sections at all.
+    ReversedRange {
+        start: usize,
+        end: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::WrongTokenCount { expected, found } => {
+                write!(f, "expected {} fields, found {}", expected, found)
+            }
+            ParseError::InvalidSection { section, .. } => {
+                write!(f, "invalid section number {:?}", section)
+            }
+            ParseError::ReversedRange { start, end } => {
+                write!(f, "section range {}-{} ends before it starts", start, end)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::WrongTokenCount { .. } | ParseError::ReversedRange { .. } => None,
+            ParseError::InvalidSection { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Splits `s` on `separator` into exactly two fields.
+fn split_pair(s: &str, separator: char) -> Result<[&str; 2], Spanned<ParseError>> {
+    s.split(separator)
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|fields: Vec<_>| {
+            let kind = ParseError::WrongTokenCount {
+                expected: 2,
+                found: fields.len(),
+            };
+
+            Spanned::at(kind, s, s)
+        })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct SectionAssignment(RangeInclusive<usize>);
+
+impl FromStr for SectionAssignment {
+    type Err = Spanned<ParseError>;
+
+    fn from_str(x: &str) -> Result<Self, Self::Err> {
+        let [start, end] = split_pair(x, '-')?.map(|section| {
+            section.parse::<usize>().map_err(|source| {
+                let kind = ParseError::InvalidSection {
+                    section: section.to_owned(),
+                    source,
+                };
+
+                Spanned::at(kind, x, section)
+            })
+        });
+
+        let (start, end) = (start?, end?);
+        if start > end {
+            return Err(Spanned::at(ParseError::ReversedRange { start, end }, x, x));
+        }
+
+        Ok(SectionAssignment(start..=end))
+    }
+}
+
+impl fmt::Display for SectionAssignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.0.start(), self.0.end())
+    }
+}
+
+impl SectionAssignment {
+    fn has_any_overlap(&self, other: &Self) -> bool {
+        let this_range = &self.0;
+        let other_range = &other.0;
+
+        !this_range.is_empty()
+            && !other_range.is_empty()
+            && this_range.start() <= other_range.end()
+            && other_range.start() <= this_range.end()
+    }
+
+    /// [`SectionAssignment::has_any_overlap`] as first written, checking every section in turn.
+    fn has_any_overlap_by_walking(&self, other: &Self) -> bool {
+        let mut this_range_nums = self.0.clone();
+        let other_range_nums = &other.0;
+
+        this_range_nums.any(|n| other_range_nums.contains(&n))
+    }
+
+    fn contains(&self, other: &Self) -> bool {
+        let this_range = &self.0;
+        let other_range = &other.0;
+
+        this_range.start() <= other_range.start() && this_range.end() >= other_range.end()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ElfAssignmentPair(SectionAssignment, SectionAssignment);
+
+impl FromStr for ElfAssignmentPair {
+    type Err = Spanned<ParseError>;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let [first, second] = split_pair(line, ',')?.map(|assignment| {
+            assignment
+                .parse::<SectionAssignment>()
+                .map_err(|e| e.within(line, assignment))
+        });
+
+        Ok(ElfAssignmentPair(first?, second?))
+    }
+}
+
+impl fmt::Display for ElfAssignmentPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.0, self.1)
+    }
+}
+
+impl ElfAssignmentPair {
+    fn is_fully_overlapping(&self) -> bool {
+        self.0.contains(&self.1) || self.1.contains(&self.0)
+    }
+
+    fn has_any_overlap(&self) -> bool {
+        self.0.has_any_overlap(&self.1)
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Vec<ElfAssignmentPair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = InputError<ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        parse_assignments(input).map_err(|e| e.locate(input))
+    }
+
+    fn part1(assignments: &Self::Parsed<'_>) -> Self::Answer1 {
+        count_fully_overlapping_assignments(assignments)
+    }
+
+    fn part2(assignments: &Self::Parsed<'_>) -> Self::Answer2 {
+        count_any_overlapping_assignments(assignments)
+    }
+}
+
+impl Streaming for Day04 {
+    type ParseError = ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseError>> {
+        let mut lines = LineReader::new(reader);
+        let (mut fully_overlapping, mut any_overlapping) = (0, 0);
+
+        while let Some(line) = lines.next_line()? {
+            let assignment = line
+                .parse::<ElfAssignmentPair>()
+                .map_err(|e| lines.locate(e))?;
+
+            fully_overlapping += usize::from(assignment.is_fully_overlapping());
+            any_overlapping += usize::from(assignment.has_any_overlap());
+        }
+
+        Ok((fully_overlapping, any_overlapping))
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day04>(Part::One),
+    Implementation::solution::<Day04>(Part::Two),
+    Implementation::streaming::<Day04>(Part::One),
+    Implementation::streaming::<Day04>(Part::Two),
+    Implementation::new(Part::Two, "walking sections", count_any_overlaps_by_walking),
+];
+
+fn count_any_overlaps_by_walking(input: &str) -> Result<Answer, String> {
+    let assignments = Day04::parse(input).map_err(|e| e.to_string())?;
+    let count = assignments
+        .iter()
+        .filter(|ElfAssignmentPair(first, second)| first.has_any_overlap_by_walking(second))
+        .count();
+
+    Ok(Answer::from(count))
+}
+
+fn parse_assignments(input: &str) -> Result<Vec<ElfAssignmentPair>, Spanned<ParseError>> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: Spanned<_>| e.within(input, line)))
+        .collect()
+}
+
+fn count_fully_overlapping_assignments(assignments: &[ElfAssignmentPair]) -> usize {
+    assignments
+        .iter()
+        .filter(|assignment| assignment.is_fully_overlapping())
+        .count()
+}
+
+fn count_any_overlapping_assignments(assignments: &[ElfAssignmentPair]) -> usize {
+    assignments
+        .iter()
+        .filter(|assignment| assignment.has_any_overlap())
+        .count()
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use std::ops::RangeInclusive;
+
+    use aoc::differential::cross_check;
+    use aoc::{Rng, Solution, StreamError, Streaming};
+
+    use crate::{
+        count_any_overlapping_assignments, count_fully_overlapping_assignments, parse_assignments,
+        Day04, ElfAssignmentPair, ParseError, SectionAssignment, IMPLEMENTATIONS,
+    };
+
+    static SAMPLE_INPUT: &str = r#"2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8"#;
+
+    fn section(s: &str) -> SectionAssignment {
+        s.parse().unwrap()
+    }
+
+    fn random_section(rng: &mut Rng) -> SectionAssignment {
+        let start = rng.range(1..=99);
+        let end = rng.range(start..=99);
+
+        SectionAssignment(start as usize..=end as usize)
+    }
+
+    fn random_pair(rng: &mut Rng) -> ElfAssignmentPair {
+        ElfAssignmentPair(random_section(rng), random_section(rng))
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let pair = random_pair(&mut rng);
+
+            assert_eq!(pair.0.to_string().parse(), Ok(pair.0.clone()));
+            assert_eq!(pair.to_string().parse(), Ok(pair));
+        }
+    }
+
+    #[test]
+    fn test_display_is_canonical() {
+        assert_eq!(section("007-10").to_string(), "7-10");
+        assert_eq!(
+            "02-4,6-08"
+                .parse::<ElfAssignmentPair>()
+                .unwrap()
+                .to_string(),
+            "2-4,6-8"
+        );
+    }
+
+    #[test]
+    fn test_overlap_properties() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..1000 {
+            let pair = random_pair(&mut rng);
+            let ElfAssignmentPair(first, second) = &pair;
+
+            if pair.is_fully_overlapping() {
+                assert!(pair.has_any_overlap(), "{}", pair);
+            }
+            assert_eq!(first.has_any_overlap(second), second.has_any_overlap(first));
+            assert!(first.contains(first) && first.has_any_overlap(first));
+        }
+    }
+
+    #[test]
+    fn test_reversed_ranges_are_rejected() {
+        let mut rng = Rng::new(2);
+
+        for _ in 0..1000 {
+            let sections = [0; 4].map(|_| rng.range(1..=99));
+            let line = format!(
+                "{}-{},{}-{}",
+                sections[0], sections[1], sections[2], sections[3]
+            );
+
+            match line.parse::<ElfAssignmentPair>() {
+                Ok(pair) => {
+                    assert!(sections[0] <= sections[1] && sections[2] <= sections[3]);
+                    if pair.is_fully_overlapping() {
+                        assert!(pair.has_any_overlap(), "{}", pair);
+                    }
+                }
+                Err(error) => {
+                    assert!(sections[0] > sections[1] || sections[2] > sections[3]);
+                    assert!(
+                        matches!(error.kind, ParseError::ReversedRange { .. }),
+                        "{}",
+                        line
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_single_section_assignment() {
+        let assignment_str = "20-30";
+        assert_eq!(section(assignment_str), SectionAssignment(20..=30));
+    }
+
+    #[test]
+    fn test_parse_invalid_section_assignment() {
+        let error = "2-x".parse::<SectionAssignment>().unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ParseError::InvalidSection { ref section, .. } if section == "x"
+        ));
+        assert_eq!(error.span, 2..3);
+
+        assert_eq!(
+            "2-4-6".parse::<SectionAssignment>().map_err(|e| e.kind),
+            Err(ParseError::WrongTokenCount {
+                expected: 2,
+                found: 3
+            })
+        );
+        let error = "5-3".parse::<SectionAssignment>().unwrap_err();
+        assert_eq!(error.kind, ParseError::ReversedRange { start: 5, end: 3 });
+        assert_eq!(error.span, 0..3);
+
+        assert_eq!(
+            "2-4".parse::<ElfAssignmentPair>().map_err(|e| e.kind),
+            Err(ParseError::WrongTokenCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_location() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-x";
+        let error = parse_assignments(input).unwrap_err().locate(input);
+
+        assert_eq!(error.line, 3);
+        assert_eq!(error.columns, 6..7);
+        assert_eq!(error.text, "5-7,7-x");
+
+        let input = "2-4,6-8\n2-8,5-3";
+        let error = parse_assignments(input).unwrap_err().locate(input);
+
+        assert_eq!(error.kind, ParseError::ReversedRange { start: 5, end: 3 });
+        assert_eq!(error.line, 2);
+        assert_eq!(error.columns, 4..7);
+    }
+
+    #[test]
+    fn test_contains() {
+        assert_eq!(section("2-4").contains(&section("6-8")), false);
+        assert_eq!(section("2-8").contains(&section("3-7")), true);
+        assert_eq!(section("6-6").contains(&section("4-6")), false);
+        assert_eq!(section("4-6").contains(&section("6-6")), true);
+        assert_eq!(section("3-7").contains(&section("2-8")), false);
+    }
+
+    #[test]
+    fn test_parse_assignment_pair() {
+        let assignment_pair_str = "20-30,40-50";
+        assert_eq!(
+            assignment_pair_str.parse::<ElfAssignmentPair>().unwrap(),
+            ElfAssignmentPair(SectionAssignment(20..=30), SectionAssignment(40..=50))
+        );
+    }
+
+    #[test]
+    fn test_is_overlapping_assignment() {
+        assert_eq!(
+            parse_assignments(SAMPLE_INPUT)
+                .unwrap()
+                .iter()
+                .map(|p| p.is_fully_overlapping())
+                .collect::<Vec<_>>(),
+            vec![false, false, false, true, true, false]
+        )
+    }
+
+    #[test]
+    fn test_count_fully_overlapping_assignments() {
+        let assignments = parse_assignments(SAMPLE_INPUT).unwrap();
+        assert_eq!(count_fully_overlapping_assignments(&assignments), 2);
+    }
+
+    #[test]
+    fn test_has_any_overlap() {
+        assert_eq!(section("2-4").has_any_overlap(&section("6-8")), false);
+        assert_eq!(section("2-8").has_any_overlap(&section("3-7")), true);
+        assert_eq!(section("6-6").has_any_overlap(&section("4-6")), true);
+        assert_eq!(section("4-6").has_any_overlap(&section("6-6")), true);
+        assert_eq!(section("3-7").has_any_overlap(&section("2-8")), true);
+        // Parsing rejects reversed ranges, but an empty range still overlaps nothing.
+        let empty = SectionAssignment(RangeInclusive::new(5, 3));
+        assert_eq!(empty.has_any_overlap(&section("2-8")), false);
+    }
+
+    #[test]
+    fn test_has_any_overlap_by_walking() {
+        for (first, second) in [
+            (section("2-4"), section("6-8")),
+            (section("2-8"), section("3-7")),
+            (section("6-6"), section("4-6")),
+            (SectionAssignment(RangeInclusive::new(5, 3)), section("2-8")),
+        ] {
+            assert_eq!(
+                first.has_any_overlap_by_walking(&second),
+                first.has_any_overlap(&second)
+            );
+        }
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day04, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+
+    #[test]
+    fn test_assignment_pair_has_any_overlap() {
+        assert_eq!(
+            parse_assignments(SAMPLE_INPUT)
+                .unwrap()
+                .iter()
+                .map(|p| p.has_any_overlap())
+                .collect::<Vec<_>>(),
+            vec![false, false, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_count_any_overlapping_assignments() {
+        let assignments = parse_assignments(SAMPLE_INPUT).unwrap();
+        assert_eq!(count_any_overlapping_assignments(&assignments), 4);
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        assert_eq!(
+            Day04::solve_stream(SAMPLE_INPUT.as_bytes()).unwrap(),
+            (2, 4)
+        );
+
+        let Err(StreamError::Input(error)) = Day04::solve_stream("2-4,6-8\n2-3;4-5\n".as_bytes())
+        else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.line, 2);
+        assert_eq!(error.text, "2-3;4-5");
+    }
+
+    #[test]
+    fn test_solve_stream_trailing_blank_lines() {
+        let input = format!("{}\n\n", SAMPLE_INPUT);
+        let normalized = aoc::input::normalize(&input);
+        let parsed = Day04::parse(&normalized).unwrap();
+
+        assert_eq!(
+            Day04::solve_stream(input.as_bytes()).unwrap(),
+            (Day04::part1(&parsed), Day04::part2(&parsed))
+        );
+    }
+}
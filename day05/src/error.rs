@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    /// There is no blank line separating the crate drawing from the procedures.
+    MissingProcedures,
+    MissingStackLabels,
+    InvalidStackLabel(String),
+    MalformedCrate(String),
+    WrongTokenCount {
+        expected: usize,
+        found: usize,
+    },
+    /// A procedure with some other word where `move`, `from` or `to` belongs.
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    InvalidNumber {
+        field: String,
+        source: ParseIntError,
+    },
+    /// A stack number, as written in the input, that doesn't name any stack.
+    StackIndexOutOfRange {
+        stack: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingProcedures => write!(
+                f,
+                "expected a blank line between the crate stacks and the procedures"
+            ),
+            ParseError::MissingStackLabels => write!(f, "missing the stack number line"),
+            ParseError::InvalidStackLabel(s) => write!(f, "invalid stack number {:?}", s),
+            ParseError::MalformedCrate(s) => {
+                write!(f, "malformed crate {:?}, expected e.g. \"[A]\"", s)
+            }
+            ParseError::WrongTokenCount { expected, found } => {
+                write!(f, "expected {} tokens, found {}", expected, found)
+            }
+            ParseError::UnexpectedToken { expected, found } => {
+                write!(f, "expected {:?}, found {:?}", expected, found)
+            }
+            ParseError::InvalidNumber { field, .. } => write!(f, "invalid number {:?}", field),
+            ParseError::StackIndexOutOfRange { stack } => write!(f, "there is no stack {}", stack),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::Day05;
+
+impl Generate for Day05 {
+    /// `size` is the number of procedures. There are 3 to 9 stacks, and no procedure ever empties
+    /// one, so that every stack has a top at the end.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<String, String> {
+        let num_stacks = rng.range(3..=9) as usize;
+
+        // Bottom crate first.
+        let mut stacks = (0..num_stacks)
+            .map(|_| {
+                (0..rng.range(1..=8))
+                    .map(|_| random_crate(rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if stacks.iter().all(|stack| stack.len() == 1) {
+            // Otherwise there would be nothing to move.
+            stacks[0].push(random_crate(rng));
+        }
+
+        let mut input = drawing(&stacks);
+        input.push('\n');
+
+        let mut stacks_9000 = stacks.clone();
+        let mut stacks_9001 = stacks;
+
+        for _ in 0..size {
+            // Both models move the same number of crates, so the stack heights always agree.
+            let movable = (0..num_stacks)
+                .filter(|&idx| stacks_9000[idx].len() > 1)
+                .collect::<Vec<_>>();
+            let from = *rng.choose(&movable);
+            let to = (from + 1 + rng.below(num_stacks - 1)) % num_stacks;
+            let quantity = rng.range(1..=stacks_9000[from].len() as u64 - 1) as usize;
+
+            // The CrateMover 9000 moves crates one at a time, which reverses them.
+            let at = stacks_9000[from].len() - quantity;
+            let moved = stacks_9000[from].split_off(at);
+            stacks_9000[to].extend(moved.into_iter().rev());
+
+            let moved = stacks_9001[from].split_off(at);
+            stacks_9001[to].extend(moved);
+
+            input.push_str(&format!(
+                "move {} from {} to {}\n",
+                quantity,
+                from + 1,
+                to + 1
+            ));
+        }
+
+        Generated {
+            input,
+            answer1: tops(&stacks_9000),
+            answer2: tops(&stacks_9001),
+        }
+    }
+}
+
+fn random_crate(rng: &mut Rng) -> char {
+    char::from(b'A' + rng.below(26) as u8)
+}
+
+/// Draws `stacks` the way the puzzle does, with the stack labels underneath.
+fn drawing(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut drawing = String::new();
+
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| {
+                stack
+                    .get(level)
+                    .map_or(String::from("   "), |c| format!("[{}]", c))
+            })
+            .collect::<Vec<_>>();
+        drawing.push_str(&row.join(" "));
+        drawing.push('\n');
+    }
+
+    let labels = (1..=stacks.len())
+        .map(|label| format!(" {} ", label))
+        .collect::<Vec<_>>();
+    drawing.push_str(&labels.join(" "));
+    drawing.push('\n');
+
+    drawing
+}
+
+fn tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().map(|stack| stack[stack.len() - 1]).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_drawing() {
+        let stacks = [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+
+        assert_eq!(
+            drawing(&stacks),
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n"
+        );
+    }
+}
@@ -1,5 +1,10 @@
 use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
 
+use aoc::Spanned;
+
+use crate::error::ParseError;
 use crate::procedure::Procedure;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -12,12 +17,28 @@ pub enum CrateMoverModel {
 pub struct Crate(char);
 
 impl Crate {
-    fn try_from(x: &str) -> Option<Self> {
-        if x.trim().is_empty() {
-            return None;
+    /// Parses one cell of the drawing, which is either a crate like `[A]` or blank.
+    fn parse(cell: &str) -> Result<Option<Self>, Spanned<ParseError>> {
+        let x = cell.trim();
+        if x.is_empty() {
+            return Ok(None);
+        }
+
+        let mut chars = x.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('['), Some(c), Some(']'), None) => Ok(Some(Crate(c))),
+            _ => Err(Spanned::at(
+                ParseError::MalformedCrate(x.to_owned()),
+                cell,
+                x,
+            )),
         }
+    }
+}
 
-        Some(Crate(x.chars().nth(1).unwrap()))
+impl fmt::Display for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.0)
     }
 }
 
@@ -37,12 +58,9 @@ impl CrateStack {
         self.0.push_front(c);
     }
 
-    fn pop(&mut self) -> Option<Crate> {
-        self.0.pop_front()
-    }
-
-    pub fn peek_inner(&self) -> char {
-        self.0.front().unwrap().0
+    /// The letter on the top crate, or `None` for an empty stack.
+    pub fn peek_inner(&self) -> Option<char> {
+        self.0.front().map(|c| c.0)
     }
 }
 
@@ -60,18 +78,23 @@ impl IntoIterator for CrateStack {
 pub struct CrateStacks(Vec<CrateStack>);
 
 impl CrateStacks {
-    pub fn do_procedure(&mut self, model: CrateMoverModel, procedure: &Procedure) {
-        let mut popped_crates: VecDeque<Crate> = VecDeque::with_capacity(procedure.quantity);
-
-        for _ in 0..procedure.quantity {
-            let c = self.0[procedure.from_stack].pop();
-
-            match c {
-                None => continue,
-                Some(c) => popped_crates.push_back(c),
+    /// Checks that `procedure` only refers to stacks that exist.
+    pub fn check_procedure(&self, procedure: &Procedure) -> Result<(), ParseError> {
+        for stack in [procedure.from_stack, procedure.to_stack] {
+            if stack >= self.0.len() {
+                return Err(ParseError::StackIndexOutOfRange { stack: stack + 1 });
             }
         }
 
+        Ok(())
+    }
+
+    /// Moving more crates than the stack holds moves the whole stack.
+    pub fn do_procedure(&mut self, model: CrateMoverModel, procedure: &Procedure) {
+        let from = &mut self.0[procedure.from_stack];
+        let quantity = procedure.quantity.min(from.0.len());
+        let mut popped_crates = from.0.drain(..quantity).collect::<VecDeque<_>>();
+
         match model {
             CrateMoverModel::CrateMover9000 => {
                 for c in popped_crates {
@@ -87,33 +110,95 @@ impl CrateStacks {
     }
 }
 
-impl From<&str> for CrateStacks {
-    fn from(lines: &str) -> Self {
-        let num_cols = (lines.lines().last().unwrap().trim().chars().last().unwrap() as usize) - 48;
-        let num_lines_in_input = lines.lines().count();
+impl FromStr for CrateStacks {
+    type Err = Spanned<ParseError>;
+
+    fn from_str(drawing: &str) -> Result<Self, Self::Err> {
+        let lines = drawing.lines().collect::<Vec<_>>();
+        let Some((&labels, rows)) = lines.split_last() else {
+            return Err(Spanned::at(
+                ParseError::MissingStackLabels,
+                drawing,
+                drawing,
+            ));
+        };
+
+        let num_cols = labels
+            .split_ascii_whitespace()
+            .map(|label| {
+                label.parse::<usize>().map_err(|_| {
+                    let kind = ParseError::InvalidStackLabel(label.to_owned());
+                    Spanned::at(kind, drawing, label)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .len();
+        if num_cols == 0 {
+            return Err(Spanned::at(ParseError::MissingStackLabels, drawing, labels));
+        }
 
         let mut stacks = CrateStacks(vec![CrateStack::new(); num_cols]);
 
-        for line in lines.lines().take(num_lines_in_input - 1) {
-            let crates = line
-                .chars()
-                .collect::<Vec<_>>()
-                .chunks(4)
-                .map(|chunk| Crate::try_from(&chunk.iter().collect::<String>()))
-                .collect::<Vec<_>>();
+        for line in rows {
+            for (idx, cell) in cells(line).into_iter().enumerate() {
+                let c = Crate::parse(cell).map_err(|e| e.within(drawing, cell))?;
 
-            for (idx, &c) in crates.iter().enumerate() {
                 match c {
                     None => continue,
+                    Some(_) if idx >= num_cols => {
+                        let kind = ParseError::StackIndexOutOfRange { stack: idx + 1 };
+                        return Err(Spanned::at(kind, drawing, cell.trim()));
+                    }
                     Some(c) => stacks.0[idx].insert(c),
                 }
             }
         }
 
-        stacks
+        Ok(stacks)
     }
 }
 
+/// Draws the stacks the way the puzzle does, labels included, which parses back to the same
+/// stacks. Like the drawing in the input, it doesn't end with a newline.
+impl fmt::Display for CrateStacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.0.iter().map(|stack| stack.0.len()).max().unwrap_or(0);
+
+        // Levels count up from the floor, while stacks keep their top crate first.
+        for level in (0..height).rev() {
+            let row = self
+                .0
+                .iter()
+                .map(|stack| match stack.0.len().checked_sub(level + 1) {
+                    Some(idx) => stack.0[idx].to_string(),
+                    None => String::from("   "),
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        let labels = (1..=self.0.len())
+            .map(|label| format!(" {} ", label))
+            .collect::<Vec<_>>();
+        write!(f, "{}", labels.join(" "))
+    }
+}
+
+/// Splits a row of the drawing into its 4-character-wide cells.
+fn cells(row: &str) -> Vec<&str> {
+    let mut starts = row
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .step_by(4)
+        .collect::<Vec<_>>();
+    starts.push(row.len());
+
+    starts
+        .windows(2)
+        .map(|bounds| &row[bounds[0]..bounds[1]])
+        .collect()
+}
+
 impl IntoIterator for CrateStacks {
     type Item = CrateStack;
 
@@ -126,14 +211,51 @@ impl IntoIterator for CrateStacks {
 
 #[cfg(test)]
 mod test {
+    use aoc::Rng;
+
     use super::*;
 
+    static SAMPLE_DRAWING: &str = r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 "#;
+
+    fn random_stacks(rng: &mut Rng) -> CrateStacks {
+        let stacks = (0..rng.range(1..=9))
+            .map(|_| {
+                let crates = (0..rng.range(0..=8))
+                    .map(|_| Crate(char::from(b'A' + rng.below(26) as u8)))
+                    .collect();
+                CrateStack(crates)
+            })
+            .collect();
+
+        CrateStacks(stacks)
+    }
+
+    fn crate_count(stacks: &CrateStacks) -> usize {
+        stacks.0.iter().map(|stack| stack.0.len()).sum()
+    }
+
     #[test]
     fn test_crate_from_str() {
-        assert_eq!(Crate::try_from("[D]"), Some(Crate('D')));
-        assert_eq!(Crate::try_from("[D] "), Some(Crate('D')));
-        assert_eq!(Crate::try_from("    "), None);
-        assert_eq!(Crate::try_from("   "), None);
+        assert_eq!(Crate::parse("[D]"), Ok(Some(Crate('D'))));
+        assert_eq!(Crate::parse("[D] "), Ok(Some(Crate('D'))));
+        assert_eq!(Crate::parse("    "), Ok(None));
+        assert_eq!(Crate::parse("   "), Ok(None));
+        assert_eq!(
+            Crate::parse(" [D"),
+            Err(Spanned {
+                kind: ParseError::MalformedCrate(String::from("[D")),
+                span: 1..3
+            })
+        );
+    }
+
+    #[test]
+    fn test_cells() {
+        assert_eq!(cells("    [D]    "), vec!["    ", "[D] ", "   "]);
+        assert_eq!(cells(""), Vec::<&str>::new());
     }
 
     #[test]
@@ -166,20 +288,91 @@ mod test {
         stacks[2].insert(Crate('P'));
 
         assert_eq!(
-            CrateStacks::from(input).into_iter().collect::<Vec<_>>(),
+            input
+                .parse::<CrateStacks>()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
             stacks
         );
     }
 
+    #[test]
+    fn test_display_stacks() {
+        let stacks = SAMPLE_DRAWING.parse::<CrateStacks>().unwrap();
+
+        assert_eq!(stacks.to_string(), SAMPLE_DRAWING);
+    }
+
+    #[test]
+    fn test_display_stacks_round_trips() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let stacks = random_stacks(&mut rng);
+
+            assert_eq!(stacks.to_string().parse(), Ok(stacks));
+        }
+    }
+
+    #[test]
+    fn test_procedures_keep_every_crate() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..200 {
+            let stacks = random_stacks(&mut rng);
+            let num_stacks = stacks.0.len();
+            let (mut stacks_9000, mut stacks_9001) = (stacks.clone(), stacks.clone());
+
+            for _ in 0..20 {
+                let procedure = Procedure {
+                    quantity: rng.range(0..=10) as usize,
+                    from_stack: rng.below(num_stacks),
+                    to_stack: rng.below(num_stacks),
+                };
+                stacks_9000.do_procedure(CrateMoverModel::CrateMover9000, &procedure);
+                stacks_9001.do_procedure(CrateMoverModel::CrateMover9001, &procedure);
+            }
+
+            assert_eq!(crate_count(&stacks_9000), crate_count(&stacks));
+            // Both models move the same number of crates, only in a different order.
+            for (stack_9000, stack_9001) in stacks_9000.0.iter().zip(&stacks_9001.0) {
+                assert_eq!(stack_9000.0.len(), stack_9001.0.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_stacks() {
+        assert_eq!(
+            "".parse::<CrateStacks>().map_err(|e| e.kind),
+            Err(ParseError::MissingStackLabels)
+        );
+        assert_eq!(
+            "[A] [B]\n 1   x ".parse::<CrateStacks>(),
+            Err(Spanned {
+                kind: ParseError::InvalidStackLabel(String::from("x")),
+                span: 13..14
+            })
+        );
+        assert_eq!(
+            "[A] [B] [C]\n 1   2 ".parse::<CrateStacks>(),
+            Err(Spanned {
+                kind: ParseError::StackIndexOutOfRange { stack: 3 },
+                span: 8..11
+            })
+        );
+    }
+
     #[test]
     fn test_do_procedure() {
         let input = r#"    [D]    
 [N] [C]    
 [Z] [M] [P]
  1   2   3 "#;
-        let mut parsed_stacks = CrateStacks::from(input);
+        let mut parsed_stacks = input.parse::<CrateStacks>().unwrap();
 
-        let procedure = Procedure::from("move 1 from 2 to 1");
+        let procedure = "move 1 from 2 to 1".parse().unwrap();
         parsed_stacks.do_procedure(CrateMoverModel::CrateMover9000, &procedure);
 
         let mut stacks = vec![CrateStack::new(); 3];
@@ -193,4 +386,18 @@ mod test {
 
         assert_eq!(parsed_stacks.into_iter().collect::<Vec<_>>(), stacks);
     }
+
+    #[test]
+    fn test_do_procedure_moves_at_most_the_stack() {
+        let mut crate_stacks = SAMPLE_DRAWING.parse::<CrateStacks>().unwrap();
+        let procedure = "move 99999999999999 from 1 to 3".parse().unwrap();
+
+        crate_stacks.do_procedure(CrateMoverModel::CrateMover9001, &procedure);
+
+        let tops = crate_stacks
+            .into_iter()
+            .map(|stack| stack.peek_inner())
+            .collect::<Vec<_>>();
+        assert_eq!(tops, [None, Some('D'), Some('N')]);
+    }
 }
@@ -0,0 +1,316 @@
+use std::io::BufRead;
+
+use aoc::differential::Implementation;
+use aoc::input::sections;
+use aoc::{substr_range, InputError, LineReader, Part, Solution, Spanned, StreamError, Streaming};
+pub use error::ParseError;
+use krate::{CrateMoverModel, CrateStacks};
+use procedure::Procedure;
+
+mod error;
+mod generate;
+mod krate;
+mod procedure;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = (CrateStacks, Vec<Procedure>);
+    type Answer1 = String;
+    type Answer2 = String;
+    type Error = InputError<ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        parse_input(input).map_err(|e| e.locate(input))
+    }
+
+    fn part1((crate_stacks, procedures): &Self::Parsed<'_>) -> Self::Answer1 {
+        get_crate_stack_tops(crate_stacks, procedures, CrateMoverModel::CrateMover9000)
+    }
+
+    fn part2((crate_stacks, procedures): &Self::Parsed<'_>) -> Self::Answer2 {
+        get_crate_stack_tops(crate_stacks, procedures, CrateMoverModel::CrateMover9001)
+    }
+}
+
+impl Streaming for Day05 {
+    type ParseError = ParseError;
+
+    fn solve_stream(reader: impl BufRead) -> Result<(String, String), StreamError<ParseError>> {
+        let mut lines = LineReader::new(reader);
+
+        // The drawing has to be read whole before we know which stack each crate is on, but it
+        // is only ever a few lines. It's the procedures that can go on for gigabytes.
+        let mut drawing = String::new();
+        loop {
+            match lines.next_line()? {
+                Some("") => break,
+                Some(line) => {
+                    if !drawing.is_empty() {
+                        drawing.push('\n');
+                    }
+                    drawing.push_str(line);
+                }
+                None => {
+                    let end = &drawing[drawing.len()..];
+                    let error = Spanned::at(ParseError::MissingProcedures, &drawing, end);
+                    return Err(StreamError::Input(error.locate(&drawing)));
+                }
+            }
+        }
+
+        let crate_stacks = drawing
+            .parse::<CrateStacks>()
+            .map_err(|e| StreamError::Input(e.locate(&drawing)))?;
+        let mut crate_stacks_9000 = crate_stacks.clone();
+        let mut crate_stacks_9001 = crate_stacks;
+
+        while let Some(line) = lines.next_line()? {
+            let procedure = line
+                .parse::<Procedure>()
+                .and_then(|procedure| {
+                    crate_stacks_9000
+                        .check_procedure(&procedure)
+                        .map_err(|kind| Spanned::at(kind, line, line))?;
+                    Ok(procedure)
+                })
+                .map_err(|e| lines.locate(e))?;
+
+            crate_stacks_9000.do_procedure(CrateMoverModel::CrateMover9000, &procedure);
+            crate_stacks_9001.do_procedure(CrateMoverModel::CrateMover9001, &procedure);
+        }
+
+        Ok((tops(crate_stacks_9000), tops(crate_stacks_9001)))
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day05>(Part::One),
+    Implementation::solution::<Day05>(Part::Two),
+    Implementation::streaming::<Day05>(Part::One),
+    Implementation::streaming::<Day05>(Part::Two),
+];
+
+fn parse_input(input: &str) -> Result<(CrateStacks, Vec<Procedure>), Spanned<ParseError>> {
+    let mut sections = sections(input);
+    let crate_stacks_and_cols = sections.next().unwrap_or(input);
+    let mut procedures = sections.peekable();
+    if procedures.peek().is_none() {
+        let end = &input[input.len()..];
+        return Err(Spanned::at(ParseError::MissingProcedures, input, end));
+    }
+
+    let crate_stacks = crate_stacks_and_cols
+        .parse::<CrateStacks>()
+        .map_err(|e| e.within(input, crate_stacks_and_cols))?;
+    // Another blank line splits the procedures into more sections. Like any other line, it has to
+    // be a procedure, as it does when streaming.
+    let procedures = procedures
+        .enumerate()
+        .flat_map(|(i, section)| {
+            let blank_line = substr_range(input, section)
+                .filter(|_| i > 0)
+                .map(|range| &input[range.start - 1..range.start - 1]);
+
+            blank_line.into_iter().chain(section.lines())
+        })
+        .map(|line| {
+            let procedure = line
+                .parse::<Procedure>()
+                .map_err(|e| e.within(input, line))?;
+            crate_stacks
+                .check_procedure(&procedure)
+                .map_err(|kind| Spanned::at(kind, input, line))?;
+
+            Ok(procedure)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((crate_stacks, procedures))
+}
+
+fn get_crate_stack_tops(
+    crate_stacks: &CrateStacks,
+    procedures: &[Procedure],
+    model: CrateMoverModel,
+) -> String {
+    let mut crate_stacks = crate_stacks.clone();
+
+    for procedure in procedures {
+        crate_stacks.do_procedure(model, procedure);
+    }
+
+    tops(crate_stacks)
+}
+
+fn tops(crate_stacks: CrateStacks) -> String {
+    crate_stacks
+        .into_iter()
+        .filter_map(|stack| stack.peek_inner())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use aoc::differential::cross_check;
+
+    use super::*;
+
+    static SAMPLE_INPUT: &str = r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"#;
+
+    fn get_sample_crate_stacks() -> CrateStacks {
+        r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 "#
+            .parse()
+            .unwrap()
+    }
+
+    fn get_sample_procedures() -> Vec<Procedure> {
+        r#"move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"#
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_parse_checks_procedures() {
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from 2 to 3";
+
+        let error = Day05::parse(input).unwrap_err();
+        assert_eq!(error.kind, ParseError::StackIndexOutOfRange { stack: 3 });
+        assert_eq!(error.line, 5);
+        assert_eq!(error.text, "move 1 from 2 to 3");
+
+        assert_eq!(
+            Day05::parse("[A] [B]\n 1   2 ").map_err(|e| e.kind),
+            Err(ParseError::MissingProcedures)
+        );
+    }
+
+    #[test]
+    fn test_procedures_9000() {
+        assert_eq!(
+            get_crate_stack_tops(
+                &get_sample_crate_stacks(),
+                &get_sample_procedures(),
+                CrateMoverModel::CrateMover9000
+            ),
+            String::from("CMZ")
+        );
+    }
+
+    #[test]
+    fn test_procedures_9001() {
+        assert_eq!(
+            get_crate_stack_tops(
+                &get_sample_crate_stacks(),
+                &get_sample_procedures(),
+                CrateMoverModel::CrateMover9001
+            ),
+            String::from("MCD")
+        );
+    }
+
+    #[test]
+    fn test_empty_stacks_have_no_top() {
+        let input = "    [D]\n 1   2 \n\nmove 1 from 2 to 1";
+        let (crate_stacks, procedures) = Day05::parse(input).unwrap();
+
+        assert_eq!(
+            Day05::part1(&(crate_stacks.clone(), procedures.clone())),
+            "D"
+        );
+        assert_eq!(Day05::part2(&(crate_stacks, procedures)), "D");
+        assert_eq!(
+            Day05::solve_stream(input.as_bytes()).unwrap(),
+            (String::from("D"), String::from("D"))
+        );
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        assert_eq!(
+            Day05::solve_stream(SAMPLE_INPUT.as_bytes()).unwrap(),
+            (String::from("CMZ"), String::from("MCD"))
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_trailing_blank_lines() {
+        let input = format!("{}\n\n", SAMPLE_INPUT);
+        let normalized = aoc::input::normalize(&input);
+        let parsed = Day05::parse(&normalized).unwrap();
+
+        assert_eq!(
+            Day05::solve_stream(input.as_bytes()).unwrap(),
+            (Day05::part1(&parsed), Day05::part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_huge_quantity() {
+        let input = "[A] [B]\n 1   2 \n\nmove 10000000000000 from 1 to 2\n";
+
+        assert_eq!(
+            Day05::solve_stream(input.as_bytes()).unwrap(),
+            (String::from("A"), String::from("A"))
+        );
+    }
+
+    #[test]
+    fn test_blank_line_between_procedures() {
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\n\nmove 1 from 2 to 1";
+        let error = Day05::parse(input).unwrap_err();
+        let Err(StreamError::Input(streamed)) = Day05::solve_stream(input.as_bytes()) else {
+            panic!("expected an input error");
+        };
+
+        assert_eq!(error.line, 5);
+        assert_eq!(error, streamed);
+    }
+
+    #[test]
+    fn test_solve_stream_errors() {
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from 2 to 3\n";
+        let Err(StreamError::Input(error)) = Day05::solve_stream(input.as_bytes()) else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.kind, ParseError::StackIndexOutOfRange { stack: 3 });
+        assert_eq!(error.line, 5);
+
+        let Err(StreamError::Input(error)) = Day05::solve_stream("[A] [B]\n 1   2 ".as_bytes())
+        else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.kind, ParseError::MissingProcedures);
+        assert_eq!(error.line, 2);
+
+        let input = "[A] [B]\n 1  x \n\nmove 1 from 1 to 2\n";
+        let Err(StreamError::Input(error)) = Day05::solve_stream(input.as_bytes()) else {
+            panic!("expected an input error");
+        };
+        assert_eq!(error.kind, ParseError::InvalidStackLabel(String::from("x")));
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day05, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+}
@@ -1,3 +1,10 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::Spanned;
+
+use crate::error::ParseError;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct Procedure {
     pub quantity: usize,
@@ -5,35 +12,145 @@ pub struct Procedure {
     pub to_stack: usize,
 }
 
-impl From<&str> for Procedure {
-    fn from(line: &str) -> Self {
-        let [_, quantity, _, from_stack, _, to_stack]: [&str; 6] = line
-            .split_ascii_whitespace()
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
-
-        Self {
-            quantity: quantity.parse().unwrap(),
-            from_stack: from_stack.parse::<usize>().unwrap() - 1,
-            to_stack: to_stack.parse::<usize>().unwrap() - 1,
+impl FromStr for Procedure {
+    type Err = Spanned<ParseError>;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let tokens = line.split_ascii_whitespace().collect::<Vec<_>>();
+        let [verb, quantity, from, from_stack, to, to_stack]: [&str; 6] =
+            tokens.try_into().map_err(|tokens: Vec<_>| {
+                let kind = ParseError::WrongTokenCount {
+                    expected: 6,
+                    found: tokens.len(),
+                };
+
+                Spanned::at(kind, line, line)
+            })?;
+
+        for (keyword, token) in [("move", verb), ("from", from), ("to", to)] {
+            if token != keyword {
+                let kind = ParseError::UnexpectedToken {
+                    expected: keyword,
+                    found: token.to_owned(),
+                };
+
+                return Err(Spanned::at(kind, line, token));
+            }
         }
+
+        Ok(Self {
+            quantity: parse_number(quantity).map_err(|kind| Spanned::at(kind, line, quantity))?,
+            from_stack: parse_stack(from_stack)
+                .map_err(|kind| Spanned::at(kind, line, from_stack))?,
+            to_stack: parse_stack(to_stack).map_err(|kind| Spanned::at(kind, line, to_stack))?,
+        })
     }
 }
 
+/// Writes the procedure the way the input does, with 1-based stack numbers.
+impl fmt::Display for Procedure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity,
+            self.from_stack + 1,
+            self.to_stack + 1
+        )
+    }
+}
+
+fn parse_number(field: &str) -> Result<usize, ParseError> {
+    field.parse().map_err(|source| ParseError::InvalidNumber {
+        field: field.to_owned(),
+        source,
+    })
+}
+
+/// Parses a 1-based stack number into an index.
+fn parse_stack(field: &str) -> Result<usize, ParseError> {
+    let stack = parse_number(field)?;
+
+    stack
+        .checked_sub(1)
+        .ok_or(ParseError::StackIndexOutOfRange { stack })
+}
+
 #[cfg(test)]
 mod test {
+    use aoc::Rng;
+
     use super::*;
 
     #[test]
     fn test_procedure_from_str() {
         assert_eq!(
-            Procedure::from("move 10 from 1 to 29"),
-            Procedure {
+            "move 10 from 1 to 29".parse(),
+            Ok(Procedure {
                 quantity: 10,
                 from_stack: 0,
                 to_stack: 28
-            }
+            })
+        );
+    }
+
+    #[test]
+    fn test_procedure_display_round_trips() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let procedure = Procedure {
+                quantity: rng.range(0..=100) as usize,
+                from_stack: rng.below(20),
+                to_stack: rng.below(20),
+            };
+
+            assert_eq!(procedure.to_string().parse(), Ok(procedure));
+        }
+
+        let procedure = "move  010 from\t1 to 09".parse::<Procedure>().unwrap();
+        assert_eq!(procedure.to_string(), "move 10 from 1 to 9");
+    }
+
+    #[test]
+    fn test_invalid_procedures() {
+        assert_eq!(
+            "move 10 from 1".parse::<Procedure>(),
+            Err(Spanned {
+                kind: ParseError::WrongTokenCount {
+                    expected: 6,
+                    found: 4
+                },
+                span: 0..14
+            })
+        );
+
+        let error = "move x from 1 to 2".parse::<Procedure>().unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ParseError::InvalidNumber { ref field, .. } if field == "x"
+        ));
+        assert_eq!(error.span, 5..6);
+
+        assert_eq!(
+            "foo 1 bar 1 baz 2".parse::<Procedure>(),
+            Err(Spanned {
+                kind: ParseError::UnexpectedToken {
+                    expected: "move",
+                    found: String::from("foo")
+                },
+                span: 0..3
+            })
+        );
+        let error = "move 1 from 1 into 2".parse::<Procedure>().unwrap_err();
+        assert_eq!(error.span, 14..18);
+
+        assert_eq!(
+            "move 1 from 0 to 2".parse::<Procedure>(),
+            Err(Spanned {
+                kind: ParseError::StackIndexOutOfRange { stack: 0 },
+                span: 12..13
+            })
         );
     }
 }
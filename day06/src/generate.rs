@@ -0,0 +1,104 @@
+use aoc::{Generate, Generated, Rng};
+
+use crate::Day06;
+
+/// Everything before the start-of-message marker is drawn from these, so no 14 characters in a
+/// row can all be different before it.
+static SHORT_ALPHABET: &[u8] = b"abcdefghijklm";
+
+impl Generate for Day06 {
+    /// `size` is the length of the datastream, which is at least 40.
+    fn generate(rng: &mut Rng, size: usize) -> Generated<usize, usize> {
+        let len = size.max(40);
+        let packet_marker = rng.range(5..=len as u64 / 2) as usize;
+        let message_marker = rng.range(packet_marker as u64 + 15..=len as u64) as usize;
+
+        datastream_with_markers(rng, packet_marker, message_marker, len)
+    }
+}
+
+/// A datastream of `len` characters whose first start-of-packet marker ends after
+/// `packet_marker` characters, and whose first start-of-message marker ends after
+/// `message_marker`.
+///
+/// Each marker is preceded by a repeat of its own first character, so that every window that
+/// ends before it sees a duplicate. That needs `5 <= packet_marker`,
+/// `packet_marker + 15 <= message_marker` and `message_marker <= len`.
+pub fn datastream_with_markers(
+    rng: &mut Rng,
+    packet_marker: usize,
+    message_marker: usize,
+    len: usize,
+) -> Generated<usize, usize> {
+    assert!(
+        5 <= packet_marker && packet_marker + 15 <= message_marker && message_marker <= len,
+        "markers at {} and {} don't fit in {} characters",
+        packet_marker,
+        message_marker,
+        len
+    );
+
+    let mut stream = Vec::with_capacity(len + 1);
+
+    // Three letters can't make four different ones in a row.
+    let packet_alphabet = &SHORT_ALPHABET[..3];
+    while stream.len() < packet_marker - 5 {
+        stream.push(*rng.choose(packet_alphabet));
+    }
+    let first = *rng.choose(packet_alphabet);
+    stream.extend(marker(rng, 4, first, SHORT_ALPHABET));
+
+    while stream.len() < message_marker - 15 {
+        stream.push(*rng.choose(SHORT_ALPHABET));
+    }
+    let all_letters = (b'a'..=b'z').collect::<Vec<_>>();
+    let first = *rng.choose(SHORT_ALPHABET);
+    stream.extend(marker(rng, 14, first, &all_letters));
+
+    while stream.len() < len {
+        stream.push(*rng.choose(&all_letters));
+    }
+    stream.push(b'\n');
+
+    Generated {
+        input: String::from_utf8(stream).unwrap(),
+        answer1: packet_marker,
+        answer2: message_marker,
+    }
+}
+
+/// `first` twice, then `size - 1` more letters from `alphabet` that are all different from it
+/// and from each other.
+fn marker(rng: &mut Rng, size: usize, first: u8, alphabet: &[u8]) -> Vec<u8> {
+    let mut others = alphabet
+        .iter()
+        .copied()
+        .filter(|&c| c != first)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut others);
+
+    [first, first]
+        .into_iter()
+        .chain(others.into_iter().take(size - 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use aoc::Solution;
+
+    use super::*;
+
+    #[test]
+    fn test_datastream_with_markers() {
+        let mut rng = Rng::new(0);
+
+        for (packet_marker, message_marker, len) in [(5, 20, 20), (7, 30, 100), (50, 65, 70)] {
+            let generated = datastream_with_markers(&mut rng, packet_marker, message_marker, len);
+
+            assert_eq!(generated.input.len(), len + 1);
+            assert_eq!(Day06::part1(&generated.input.as_str()), packet_marker);
+            assert_eq!(Day06::part2(&generated.input.as_str()), message_marker);
+        }
+    }
+}
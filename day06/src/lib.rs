@@ -0,0 +1,345 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::io::{self, BufRead};
+
+use aoc::differential::Implementation;
+use aoc::{Answer, Part, Solution, StreamError, Streaming};
+pub use generate::datastream_with_markers;
+
+mod generate;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseError {
+    /// The datastream never has `window_size` distinct characters in a row.
+    NoMarker { window_size: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NoMarker { window_size } => write!(
+                f,
+                "no {} distinct characters in a row in the datastream",
+                window_size
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = &'a str;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = ParseError;
+
+    /// Checks the datastream has both markers. A missing marker is about the whole datastream, so
+    /// the error has no line to point at.
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error> {
+        for window_size in [4, 14] {
+            find_first_marker(input, window_size).ok_or(ParseError::NoMarker { window_size })?;
+        }
+
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Answer1 {
+        find_first_marker(input, 4).expect("parsing checks there is a marker")
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Answer2 {
+        find_first_marker(input, 14).expect("parsing checks there is a marker")
+    }
+}
+
+impl Streaming for Day06 {
+    type ParseError = ParseError;
+
+    /// Decodes the datastream a `char` at a time, so that positions count the same `char`s the
+    /// parts do, and stops as soon as both markers are found. A byte order mark at the very start
+    /// is dropped, as [`aoc::input::normalize`] would.
+    fn solve_stream(mut reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseError>> {
+        let mut packet = MarkerFinder::new(4);
+        let mut message = MarkerFinder::new(14);
+        // The bytes of a `char` that is split between two reads.
+        let mut pending = Vec::with_capacity(4);
+        let mut started = false;
+
+        while packet.found.is_none() || message.found.is_none() {
+            let buf = reader.fill_buf()?;
+            if buf.is_empty() {
+                if !pending.is_empty() {
+                    return Err(invalid_utf8().into());
+                }
+                break;
+            }
+
+            for &byte in buf {
+                pending.push(byte);
+                let c = match std::str::from_utf8(&pending) {
+                    Ok(decoded) => decoded.chars().next(),
+                    Err(e) if e.error_len().is_none() => continue,
+                    Err(_) => return Err(invalid_utf8().into()),
+                };
+                pending.clear();
+
+                if let Some(c) = c.filter(|&c| started || c != '\u{feff}') {
+                    packet.push(c);
+                    message.push(c);
+                }
+                started = true;
+            }
+
+            let len = buf.len();
+            reader.consume(len);
+        }
+
+        let marker = |finder: MarkerFinder| {
+            finder
+                .found
+                .ok_or(StreamError::Invalid(ParseError::NoMarker {
+                    window_size: finder.window_size,
+                }))
+        };
+
+        Ok((marker(packet)?, marker(message)?))
+    }
+}
+
+/// The error reading a line gives for the same bytes.
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// Looks for the first `window_size` distinct `char`s in a row, one `char` at a time.
+struct MarkerFinder {
+    window: VecDeque<char>,
+    window_size: usize,
+    position: usize,
+    found: Option<usize>,
+}
+
+impl MarkerFinder {
+    fn new(window_size: usize) -> Self {
+        MarkerFinder {
+            window: VecDeque::with_capacity(window_size + 1),
+            window_size,
+            position: 0,
+            found: None,
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        if self.found.is_some() {
+            return;
+        }
+
+        self.position += 1;
+        self.window.push_back(c);
+        if self.window.len() > self.window_size {
+            self.window.pop_front();
+        }
+
+        if self.window.len() == self.window_size && slice_is_unique(self.window.make_contiguous()) {
+            self.found = Some(self.position);
+        }
+    }
+}
+
+/// Every implementation of each part, for `aoc diff` to check against each other.
+pub static IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::solution::<Day06>(Part::One),
+    Implementation::solution::<Day06>(Part::Two),
+    Implementation::streaming::<Day06>(Part::One),
+    Implementation::streaming::<Day06>(Part::Two),
+    Implementation::new(Part::One, "set per window", |input| {
+        let marker = find_first_marker_by_sets(input, 4)
+            .ok_or_else(|| ParseError::NoMarker { window_size: 4 }.to_string())?;
+        Ok(Answer::from(marker))
+    }),
+    Implementation::new(Part::Two, "set per window", |input| {
+        let marker = find_first_marker_by_sets(input, 14)
+            .ok_or_else(|| ParseError::NoMarker { window_size: 14 }.to_string())?;
+        Ok(Answer::from(marker))
+    }),
+];
+
+/// Finds where the first `window_size` distinct characters in a row end. Rather than checking
+/// every window from scratch, the window is moved past each repeated character as it comes, so
+/// every character is looked at once.
+fn find_first_marker(input: &str, window_size: usize) -> Option<usize> {
+    let mut last_seen = HashMap::new();
+    let mut window_start = 0;
+
+    for (idx, c) in input.chars().enumerate() {
+        if let Some(previous) = last_seen.insert(c, idx) {
+            window_start = window_start.max(previous + 1);
+        }
+
+        if idx + 1 - window_start == window_size {
+            return Some(idx + 1);
+        }
+    }
+
+    None
+}
+
+/// [`find_first_marker`] as first written, building a set for every window.
+fn find_first_marker_by_sets(input: &str, window_size: usize) -> Option<usize> {
+    let (window_start_idx, _) = input
+        .chars()
+        .collect::<Vec<_>>()
+        .windows(window_size)
+        .enumerate()
+        .find(|(_, window)| slice_is_unique(window))?;
+
+    Some(window_start_idx + window_size)
+}
+
+fn slice_is_unique<T: Eq + Hash>(s: &[T]) -> bool {
+    let mut set = HashSet::new();
+    s.iter().all(|c| set.insert(c))
+}
+
+#[cfg(test)]
+mod test {
+    use aoc::differential::cross_check;
+
+    use super::*;
+
+    #[test]
+    fn test_find_first_start_of_packet_marker() {
+        assert_eq!(
+            find_first_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4),
+            Some(7)
+        );
+        assert_eq!(
+            find_first_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4),
+            Some(5)
+        );
+        assert_eq!(
+            find_first_marker("nppdvjthqldpwncqszvftbrmjlhg", 4),
+            Some(6)
+        );
+        assert_eq!(
+            find_first_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4),
+            Some(10)
+        );
+        assert_eq!(
+            find_first_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_find_first_start_of_message_marker() {
+        assert_eq!(
+            find_first_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14),
+            Some(19)
+        );
+        assert_eq!(
+            find_first_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14),
+            Some(23)
+        );
+        assert_eq!(
+            find_first_marker("nppdvjthqldpwncqszvftbrmjlhg", 14),
+            Some(23)
+        );
+        assert_eq!(
+            find_first_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14),
+            Some(29)
+        );
+        assert_eq!(
+            find_first_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14),
+            Some(26)
+        );
+    }
+
+    #[test]
+    fn test_find_first_marker_by_sets() {
+        for input in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+        ] {
+            for window_size in [4, 14] {
+                assert_eq!(
+                    find_first_marker_by_sets(input, window_size),
+                    find_first_marker(input, window_size)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        assert!(cross_check(&Day06, IMPLEMENTATIONS, 0..50, 100).is_ok());
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        for (input, expected) in [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", (7, 19)),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", (5, 23)),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n", (11, 26)),
+        ] {
+            assert_eq!(Day06::solve_stream(input.as_bytes()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_solve_stream_matches_parse() {
+        for input in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "\u{feff}mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "ééééabcdefghijklmnopq",
+            "aé€😀bcdefghijklmnopq",
+        ] {
+            let normalized = aoc::input::normalize(input);
+            let parsed = Day06::parse(&normalized).unwrap();
+
+            assert_eq!(
+                Day06::solve_stream(input.as_bytes()).unwrap(),
+                (Day06::part1(&parsed), Day06::part2(&parsed)),
+                "{:?}",
+                input
+            );
+        }
+
+        let split = "aé€😀bcdefghijklmnopq".as_bytes();
+        let reader = io::BufReader::with_capacity(1, split);
+        assert_eq!(Day06::solve_stream(reader).unwrap(), (4, 14));
+
+        let error = Day06::solve_stream(&b"abc\xffdefghijklmnop"[..]).unwrap_err();
+        assert!(matches!(error, StreamError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(
+            Day06::parse("abc"),
+            Err(ParseError::NoMarker { window_size: 4 })
+        );
+        assert_eq!(
+            Day06::parse("abcdefghijklm"),
+            Err(ParseError::NoMarker { window_size: 14 })
+        );
+
+        let error = Day06::solve_stream("abcdefghijklm".as_bytes()).unwrap_err();
+        assert!(matches!(
+            error,
+            StreamError::Invalid(ParseError::NoMarker { window_size: 14 })
+        ));
+        assert_eq!(find_first_marker_by_sets("abc", 4), None);
+    }
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day01::parse(input) {
+            Day01::part1(&parsed);
+            Day01::part2(&parsed);
+        }
+    }
+    let _ = Day01::solve_stream(data);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day02::Day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day02::parse(input) {
+            Day02::part1(&parsed);
+            Day02::part2(&parsed);
+        }
+    }
+    let _ = Day02::solve_stream(data);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day03::Day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day03::parse(input) {
+            Day03::part1(&parsed);
+            Day03::part2(&parsed);
+        }
+    }
+    let _ = Day03::solve_stream(data);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day04::Day04;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day04::parse(input) {
+            Day04::part1(&parsed);
+            Day04::part2(&parsed);
+        }
+    }
+    let _ = Day04::solve_stream(data);
+});
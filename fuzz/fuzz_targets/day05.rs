@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day05::parse(input) {
+            Day05::part1(&parsed);
+            Day05::part2(&parsed);
+        }
+    }
+    let _ = Day05::solve_stream(data);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::{Solution, Streaming};
+use day06::Day06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = Day06::parse(input) {
+            Day06::part1(&parsed);
+            Day06::part2(&parsed);
+        }
+    }
+    let _ = Day06::solve_stream(data);
+});
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc::Part;
+
+/// Accepted answers, keyed by day, part and the hash of the input they were computed from.
+///
+/// On disk this is one `<day> <part> <input hash> <answer>` line per answer.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct AnswerStore(BTreeMap<(u8, Part, u64), String>);
+
+impl AnswerStore {
+    /// The answers file checked in at the root of the workspace.
+    pub fn default_path() -> PathBuf {
+        aoc::input::workspace_root().join("answers.txt")
+    }
+
+    /// Loads the store at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: Part, input_hash: u64) -> Option<&str> {
+        self.0.get(&(day, part, input_hash)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, input_hash: u64, answer: String) {
+        self.0.insert((day, part, input_hash), answer);
+    }
+}
+
+impl FromStr for AnswerStore {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut store = AnswerStore::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("line {}: invalid answer entry {:?}", idx + 1, line);
+
+            let [day, part, input_hash, answer]: [&str; 4] = line
+                .splitn(4, ' ')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+
+            store.insert(
+                day.parse().map_err(|_| invalid())?,
+                part.parse().map_err(|_| invalid())?,
+                u64::from_str_radix(input_hash, 16).map_err(|_| invalid())?,
+                answer.to_owned(),
+            );
+        }
+
+        Ok(store)
+    }
+}
+
+impl fmt::Display for AnswerStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Accepted answers, checked by `aoc verify`.")?;
+        writeln!(f, "# day part input-hash answer")?;
+
+        for ((day, part, input_hash), answer) in &self.0 {
+            writeln!(f, "{:02} {} {:016x} {}", day, part, input_hash, answer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut store = AnswerStore::default();
+        store.insert(5, Part::Two, 0xdeadbeef, String::from("MCD"));
+        store.insert(1, Part::One, 0x0123456789abcdef, String::from("24000"));
+
+        let text = store.to_string();
+
+        assert!(text.ends_with("01 1 0123456789abcdef 24000\n05 2 00000000deadbeef MCD\n"));
+        assert_eq!(text.parse(), Ok(store));
+    }
+
+    #[test]
+    fn test_get() {
+        let store = "01 2 00000000000000ff 45000\n"
+            .parse::<AnswerStore>()
+            .unwrap();
+
+        assert_eq!(store.get(1, Part::Two, 0xff), Some("45000"));
+        assert_eq!(store.get(1, Part::One, 0xff), None);
+        assert_eq!(store.get(1, Part::Two, 0xfe), None);
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        assert!("01 1 ff".parse::<AnswerStore>().is_err());
+        assert!("01 3 ff 1".parse::<AnswerStore>().is_err());
+        assert!("01 1 xyz 1".parse::<AnswerStore>().is_err());
+    }
+}
@@ -0,0 +1,590 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
+use aoc::input::InputSource;
+use aoc::Part;
+
+use crate::json::Format;
+
+pub static USAGE: &str = "Usage:
+    aoc run <day> [--part 1|2] [--input <path>|-] [--format text|json|ndjson]
+    aoc run --all [--part 1|2] [--format text|json|ndjson]
+    aoc run <day> --inputs <dir> [--part 1|2]
+    aoc bench [<day>] [--iterations <n>] [--input <path>|-]
+    aoc verify [<day>] [--input <path>|-] [--record]
+    aoc new <day>
+    aoc fetch <day> [--year <year>]
+    aoc submit <day> <part> [--input <path>|-]
+    aoc generate <day> [--seed <n>] [--size <n>]
+    aoc watch <day>
+    aoc diff [<day>] [--seed <n>] [--seeds <n>] [--size <n>]
+    aoc stats <day> [--input <path>|-]";
+
+const DEFAULT_BENCH_ITERATIONS: usize = 100;
+const DEFAULT_GENERATE_SIZE: usize = 1000;
+const DEFAULT_DIFF_SEEDS: u64 = 100;
+const DEFAULT_DIFF_SIZE: usize = 100;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        day: u8,
+        part: Option<Part>,
+        input: InputSource,
+        format: Format,
+    },
+    RunAll {
+        part: Option<Part>,
+        format: Format,
+    },
+    RunBatch {
+        day: u8,
+        part: Option<Part>,
+        dir: PathBuf,
+    },
+    Bench {
+        day: Option<u8>,
+        iterations: usize,
+        input: InputSource,
+    },
+    Verify {
+        day: Option<u8>,
+        input: InputSource,
+        record: bool,
+    },
+    New {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+        year: Option<u16>,
+    },
+    Submit {
+        day: u8,
+        part: Part,
+        input: InputSource,
+    },
+    Generate {
+        day: u8,
+        seed: u64,
+        size: usize,
+    },
+    Watch {
+        day: u8,
+    },
+    Diff {
+        day: Option<u8>,
+        seeds: Range<u64>,
+        size: usize,
+    },
+    Stats {
+        day: u8,
+        input: InputSource,
+    },
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    let command = match args.next().as_deref() {
+        Some("run") => {
+            let options = Options::parse(
+                args,
+                &["--part", "--input", "--format", "--all", "--inputs"],
+            )?;
+
+            if let Some(dir) = options.inputs {
+                if options.all || options.input != InputSource::Default {
+                    return Err(String::from("--inputs can't be used with --all or --input"));
+                }
+                if options.format != Format::Text {
+                    return Err(String::from("--inputs only prints a table"));
+                }
+
+                Command::RunBatch {
+                    day: options.day.ok_or("missing day")?,
+                    part: options.part,
+                    dir,
+                }
+            } else if options.all {
+                if options.day.is_some() {
+                    return Err(String::from("--all already runs every day"));
+                }
+                options.check_input_has_day()?;
+
+                Command::RunAll {
+                    part: options.part,
+                    format: options.format,
+                }
+            } else {
+                Command::Run {
+                    day: options.day.ok_or("missing day")?,
+                    part: options.part,
+                    input: options.input,
+                    format: options.format,
+                }
+            }
+        }
+        Some("bench") => {
+            let options = Options::parse(args, &["--iterations", "--input"])?;
+            options.check_input_has_day()?;
+
+            Command::Bench {
+                day: options.day,
+                iterations: options.iterations.unwrap_or(DEFAULT_BENCH_ITERATIONS),
+                input: options.input,
+            }
+        }
+        Some("verify") => {
+            let options = Options::parse(args, &["--input", "--record"])?;
+            options.check_input_has_day()?;
+
+            Command::Verify {
+                day: options.day,
+                input: options.input,
+                record: options.record,
+            }
+        }
+        Some("new") => {
+            let options = Options::parse(args, &[])?;
+
+            Command::New {
+                day: options.day.ok_or("missing day")?,
+            }
+        }
+        Some("fetch") => {
+            let options = Options::parse(args, &["--year"])?;
+
+            Command::Fetch {
+                day: options.day.ok_or("missing day")?,
+                year: options.year,
+            }
+        }
+        Some("submit") => {
+            let day = parse_day(&args.next().ok_or("missing day")?)?;
+            let part = args.next().ok_or("missing part")?.parse()?;
+            let options = Options::parse(args, &["--input"])?;
+            if let Some(extra) = options.day {
+                return Err(format!("unexpected argument {:?}", extra.to_string()));
+            }
+
+            Command::Submit {
+                day,
+                part,
+                input: options.input,
+            }
+        }
+        Some("generate") => {
+            let options = Options::parse(args, &["--seed", "--size"])?;
+
+            Command::Generate {
+                day: options.day.ok_or("missing day")?,
+                seed: options.seed.unwrap_or(0),
+                size: options.size.unwrap_or(DEFAULT_GENERATE_SIZE),
+            }
+        }
+        Some("watch") => {
+            let options = Options::parse(args, &[])?;
+
+            Command::Watch {
+                day: options.day.ok_or("missing day")?,
+            }
+        }
+        Some("diff") => {
+            let options = Options::parse(args, &["--seed", "--seeds", "--size"])?;
+            let first_seed = options.seed.unwrap_or(0);
+            let seeds = options.seeds.unwrap_or(DEFAULT_DIFF_SEEDS);
+
+            Command::Diff {
+                day: options.day,
+                seeds: first_seed..first_seed.saturating_add(seeds),
+                size: options.size.unwrap_or(DEFAULT_DIFF_SIZE),
+            }
+        }
+        Some("stats") => {
+            let options = Options::parse(args, &["--input"])?;
+
+            Command::Stats {
+                day: options.day.ok_or("missing day")?,
+                input: options.input,
+            }
+        }
+        Some(other) => return Err(format!("unknown command {:?}", other)),
+        None => return Err(String::from("missing command")),
+    };
+
+    Ok(command)
+}
+
+/// Everything that can follow a command. Each command picks the options it accepts.
+#[derive(Debug, Default)]
+struct Options {
+    day: Option<u8>,
+    part: Option<Part>,
+    input: InputSource,
+    inputs: Option<PathBuf>,
+    iterations: Option<usize>,
+    record: bool,
+    all: bool,
+    year: Option<u16>,
+    format: Format,
+    seed: Option<u64>,
+    seeds: Option<u64>,
+    size: Option<usize>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>, allowed: &[&str]) -> Result<Self, String> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            let flag = match arg.as_str() {
+                "-p" => "--part",
+                "-i" => "--input",
+                "-n" => "--iterations",
+                flag => flag,
+            };
+
+            if !flag.starts_with("--") {
+                if options.day.is_some() {
+                    return Err(format!("unexpected argument {:?}", arg));
+                }
+                options.day = Some(parse_day(&arg)?);
+                continue;
+            }
+
+            if !allowed.contains(&flag) {
+                return Err(format!("unexpected option {:?}", arg));
+            }
+
+            match flag {
+                "--part" => {
+                    let value = args.next().ok_or("--part needs a value")?;
+                    options.part = Some(value.parse()?);
+                }
+                "--input" => {
+                    let value = args.next().ok_or("--input needs a path, or - for stdin")?;
+                    options.input = InputSource::from(value.as_str());
+                }
+                "--inputs" => {
+                    let value = args.next().ok_or("--inputs needs a directory")?;
+                    options.inputs = Some(PathBuf::from(value));
+                }
+                "--iterations" => {
+                    let value = args.next().ok_or("--iterations needs a value")?;
+                    options.iterations = match value.parse() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(format!("invalid iteration count {:?}", value)),
+                    };
+                }
+                "--record" => options.record = true,
+                "--all" => options.all = true,
+                "--format" => {
+                    let value = args.next().ok_or("--format needs a value")?;
+                    options.format = value.parse()?;
+                }
+                "--seed" => {
+                    let value = args.next().ok_or("--seed needs a value")?;
+                    options.seed = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid seed {:?}", value))?,
+                    );
+                }
+                "--seeds" => {
+                    let value = args.next().ok_or("--seeds needs a value")?;
+                    options.seeds = match value.parse() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(format!("invalid seed count {:?}", value)),
+                    };
+                }
+                "--size" => {
+                    let value = args.next().ok_or("--size needs a value")?;
+                    options.size = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid size {:?}", value))?,
+                    );
+                }
+                "--year" => {
+                    let value = args.next().ok_or("--year needs a value")?;
+                    options.year = match value.parse() {
+                        Ok(year @ 2015..) => Some(year),
+                        _ => return Err(format!("invalid year {:?}", value)),
+                    };
+                }
+                _ => unreachable!("{} is allowed but not handled", flag),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Reading one input for every day makes no sense, so `--input` needs a day.
+    fn check_input_has_day(&self) -> Result<(), String> {
+        if self.day.is_none() && self.input != InputSource::Default {
+            return Err(String::from("--input can only be used with a single day"));
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u8, String> {
+    match arg.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!(
+            "invalid day {:?}, expected a number from 1 to 25",
+            arg
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_str("run 5"),
+            Ok(Command::Run {
+                day: 5,
+                part: None,
+                input: InputSource::Default,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse_str("run 05 --part 2"),
+            Ok(Command::Run {
+                day: 5,
+                part: Some(Part::Two),
+                input: InputSource::Default,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse_str("run -p 1 3 --input -"),
+            Ok(Command::Run {
+                day: 3,
+                part: Some(Part::One),
+                input: InputSource::Stdin,
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse_str("run 4 -i other/input.txt"),
+            Ok(Command::Run {
+                day: 4,
+                part: None,
+                input: InputSource::from("other/input.txt"),
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            parse_str("run 2 --format ndjson"),
+            Ok(Command::Run {
+                day: 2,
+                part: None,
+                input: InputSource::Default,
+                format: Format::Ndjson
+            })
+        );
+        assert!(parse_str("run 2 --format yaml").is_err());
+        assert_eq!(
+            parse_str("run --all -p 2 --format json"),
+            Ok(Command::RunAll {
+                part: Some(Part::Two),
+                format: Format::Json
+            })
+        );
+        assert!(parse_str("run 2 --all").is_err());
+        assert!(parse_str("run --all --input -").is_err());
+        assert!(parse_str("bench --all").is_err());
+        assert_eq!(
+            parse_str("run 5 --inputs inputs/day05 -p 1"),
+            Ok(Command::RunBatch {
+                day: 5,
+                part: Some(Part::One),
+                dir: PathBuf::from("inputs/day05")
+            })
+        );
+        assert!(parse_str("run --inputs inputs").is_err());
+        assert!(parse_str("run 5 --inputs inputs --input -").is_err());
+        assert!(parse_str("run 5 --inputs inputs --format json").is_err());
+        assert!(parse_str("bench --format json").is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse_str("bench"),
+            Ok(Command::Bench {
+                day: None,
+                iterations: DEFAULT_BENCH_ITERATIONS,
+                input: InputSource::Default
+            })
+        );
+        assert_eq!(
+            parse_str("bench 6 -n 1000 --input -"),
+            Ok(Command::Bench {
+                day: Some(6),
+                iterations: 1000,
+                input: InputSource::Stdin
+            })
+        );
+        assert!(parse_str("bench -n 0").is_err());
+        assert!(parse_str("bench --input -").is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse_str("verify"),
+            Ok(Command::Verify {
+                day: None,
+                input: InputSource::Default,
+                record: false
+            })
+        );
+        assert_eq!(
+            parse_str("verify 2 --record"),
+            Ok(Command::Verify {
+                day: Some(2),
+                input: InputSource::Default,
+                record: true
+            })
+        );
+        assert!(parse_str("verify --part 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse_str("new 7"), Ok(Command::New { day: 7 }));
+        assert!(parse_str("new").is_err());
+        assert!(parse_str("new 7 --input x").is_err());
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            parse_str("fetch 7"),
+            Ok(Command::Fetch { day: 7, year: None })
+        );
+        assert_eq!(
+            parse_str("fetch 1 --year 2021"),
+            Ok(Command::Fetch {
+                day: 1,
+                year: Some(2021)
+            })
+        );
+        assert!(parse_str("fetch 1 --year 1999").is_err());
+        assert!(parse_str("run 1 --year 2021").is_err());
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            parse_str("submit 5 2"),
+            Ok(Command::Submit {
+                day: 5,
+                part: Part::Two,
+                input: InputSource::Default
+            })
+        );
+        assert_eq!(
+            parse_str("submit 1 1 -i -"),
+            Ok(Command::Submit {
+                day: 1,
+                part: Part::One,
+                input: InputSource::Stdin
+            })
+        );
+        assert!(parse_str("submit 5").is_err());
+        assert!(parse_str("submit 5 3").is_err());
+        assert!(parse_str("submit 5 2 7").is_err());
+        assert!(parse_str("submit --part 2 5").is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse_str("generate 3"),
+            Ok(Command::Generate {
+                day: 3,
+                seed: 0,
+                size: DEFAULT_GENERATE_SIZE
+            })
+        );
+        assert_eq!(
+            parse_str("generate 6 --seed 42 --size 1000000"),
+            Ok(Command::Generate {
+                day: 6,
+                seed: 42,
+                size: 1_000_000
+            })
+        );
+        assert!(parse_str("generate 6 --seed -1").is_err());
+        assert!(parse_str("generate --size 10").is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(parse_str("watch 5"), Ok(Command::Watch { day: 5 }));
+        assert!(parse_str("watch").is_err());
+        assert!(parse_str("watch 5 --input -").is_err());
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        assert_eq!(
+            parse_str("diff"),
+            Ok(Command::Diff {
+                day: None,
+                seeds: 0..DEFAULT_DIFF_SEEDS,
+                size: DEFAULT_DIFF_SIZE
+            })
+        );
+        assert_eq!(
+            parse_str("diff 4 --seed 10 --seeds 5 --size 20"),
+            Ok(Command::Diff {
+                day: Some(4),
+                seeds: 10..15,
+                size: 20
+            })
+        );
+        assert!(parse_str("diff --seeds 0").is_err());
+        assert!(parse_str("diff 4 --input -").is_err());
+    }
+
+    #[test]
+    fn test_parse_stats() {
+        assert_eq!(
+            parse_str("stats 1 --input -"),
+            Ok(Command::Stats {
+                day: 1,
+                input: InputSource::Stdin
+            })
+        );
+        assert!(parse_str("stats").is_err());
+        assert!(parse_str("stats 1 --part 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_str("").is_err());
+        assert!(parse_str("walk 1").is_err());
+        assert!(parse_str("run").is_err());
+        assert!(parse_str("run 26").is_err());
+        assert!(parse_str("run 1 --part 3").is_err());
+        assert!(parse_str("run 1 2").is_err());
+        assert!(parse_str("run 1 --input").is_err());
+        assert!(parse_str("run 1 --iterations 5").is_err());
+        assert!(parse_str("run 1 --bogus").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc::input::InputSource;
+use aoc::{Part, Run};
+
+use crate::run_all::parallel_map;
+use crate::{find_solver, solve};
+
+/// One input file's run.
+struct FileRun {
+    name: String,
+    result: Result<Run, String>,
+}
+
+/// Runs `day` on every file in `dir`, such as everyone's inputs gathered in one place, and flags
+/// the ones that could not be solved.
+pub fn run_batch(day: u8, part: Option<Part>, dir: &Path) -> ExitCode {
+    let Some(solver) = find_solver(day) else {
+        return ExitCode::FAILURE;
+    };
+    let paths = match input_files(dir) {
+        Ok(paths) if paths.is_empty() => {
+            eprintln!("error: no inputs in {}", dir.display());
+            return ExitCode::FAILURE;
+        }
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!(
+                "error: could not list the inputs in {}: {}",
+                dir.display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let file_runs = parallel_map(&paths, |path| FileRun {
+        name: path.file_name().unwrap().to_string_lossy().into_owned(),
+        result: InputSource::Path(path.clone())
+            .read(day)
+            .map_err(|e| format!("could not read the input: {}", e))
+            .and_then(|input| solve(solver, &input, &parts)),
+    });
+
+    print!("{}", table(&file_runs, &parts));
+
+    let failed = file_runs
+        .iter()
+        .filter_map(|file_run| Some((&file_run.name, file_run.result.as_ref().err()?)))
+        .collect::<Vec<_>>();
+    for (name, e) in &failed {
+        eprintln!("error: {}: {}", name, e);
+    }
+
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{} of {} inputs failed", failed.len(), file_runs.len());
+        ExitCode::FAILURE
+    }
+}
+
+/// The files directly in `dir`, by name. Hidden files, like editor swap files, are left out.
+fn input_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+
+        if entry.file_type()?.is_file() && !hidden {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+fn table(file_runs: &[FileRun], parts: &[Part]) -> String {
+    let width = file_runs
+        .iter()
+        .map(|file_run| file_run.name.len())
+        .chain(["input".len()])
+        .max()
+        .unwrap();
+
+    let mut table = format!("  {:<width$}", "input");
+    for part in parts {
+        table.push_str(&format!(" {:<16}", format!("part {}", part)));
+    }
+    table.push_str(&format!(" {:>12}\n", "time"));
+
+    for file_run in file_runs {
+        table.push_str(&format!("  {:<width$}", file_run.name));
+
+        match &file_run.result {
+            Ok(run) => {
+                for part_run in &run.parts {
+                    table.push_str(&format!(" {:<16}", part_run.answer.to_string()));
+                }
+                let time = run.parse_time + run.parts.iter().map(|part_run| part_run.time).sum();
+                table.push_str(&format!(" {:>12}\n", format!("{:.2?}", time)));
+            }
+            Err(_) => {
+                for _ in parts {
+                    table.push_str(&format!(" {:<16}", "FAILED"));
+                }
+                table.push_str(&format!(" {:>12}\n", "-"));
+            }
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::time::Duration;
+
+    use aoc::{Answer, PartRun};
+
+    use super::*;
+
+    #[test]
+    fn test_input_files() {
+        let dir = env::temp_dir().join(format!("aoc-batch-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["carol.txt", "alice.txt", ".alice.txt.swp", "bob"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let names = input_files(&dir)
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(names, ["alice.txt", "bob", "carol.txt"]);
+        assert!(input_files(&dir).is_err());
+    }
+
+    #[test]
+    fn test_table() {
+        let file_runs = [
+            FileRun {
+                name: String::from("alice.txt"),
+                result: Ok(Run {
+                    parse_time: Duration::from_millis(1),
+                    parts: vec![PartRun {
+                        part: Part::One,
+                        answer: Answer::from(String::from("CMZ")),
+                        time: Duration::from_millis(1),
+                    }],
+                }),
+            },
+            FileRun {
+                name: String::from("bob-windows.txt"),
+                result: Err(String::from("could not parse the input for day 5")),
+            },
+        ];
+
+        let table = table(&file_runs, &[Part::One]);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("  input           part 1"));
+        assert!(lines[1].starts_with("  alice.txt       CMZ"));
+        assert!(lines[1].ends_with("2.00ms"));
+        assert!(lines[2].starts_with("  bob-windows.txt FAILED"));
+    }
+}
@@ -0,0 +1,192 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc::input::InputSource;
+use aoc::{Part, Solver};
+
+use crate::{days, find_solver, read_input, solve};
+
+/// Wall time statistics over repeated runs of one phase of a solver.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        Stats {
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+        }
+    }
+}
+
+/// The nearest-rank `p`th percentile of the already sorted `samples`.
+fn percentile(samples: &[Duration], p: usize) -> Duration {
+    let rank = (p * samples.len()).div_ceil(100).max(1);
+
+    samples[rank - 1]
+}
+
+struct DayBench {
+    day: u8,
+    parse: Stats,
+    part1: Stats,
+    part2: Stats,
+}
+
+impl DayBench {
+    fn total_median(&self) -> Duration {
+        self.parse.median + self.part1.median + self.part2.median
+    }
+}
+
+pub fn bench(day: Option<u8>, iterations: usize, source: &InputSource) -> ExitCode {
+    let solvers = match day {
+        Some(day) => match find_solver(day) {
+            Some(solver) => vec![solver],
+            None => return ExitCode::FAILURE,
+        },
+        None => days::DAYS.to_vec(),
+    };
+
+    let mut results = Vec::new();
+    let mut failed = false;
+
+    for solver in solvers {
+        let Some(input) = read_input(solver.day(), source) else {
+            failed = true;
+            continue;
+        };
+
+        match bench_day(solver, &input, iterations) {
+            Ok(result) => {
+                print_day(&result, iterations);
+                results.push(result);
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    print_summary(&results);
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_day(solver: &dyn Solver, input: &str, iterations: usize) -> Result<DayBench, String> {
+    // One untimed run up front, so that a broken solver fails before we start measuring.
+    solve(solver, input, &Part::ALL)?;
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let run = solve(solver, input, &Part::ALL)?;
+
+        parse.push(run.parse_time);
+        part1.push(run.parts[0].time);
+        part2.push(run.parts[1].time);
+    }
+
+    Ok(DayBench {
+        day: solver.day(),
+        parse: Stats::from_samples(parse),
+        part1: Stats::from_samples(part1),
+        part2: Stats::from_samples(part2),
+    })
+}
+
+fn print_day(result: &DayBench, iterations: usize) {
+    println!("Day {:02} ({} iterations)", result.day, iterations);
+    println!(
+        "  {:<8} {:>12} {:>12} {:>12}",
+        "phase", "min", "median", "p95"
+    );
+
+    for (phase, stats) in [
+        ("parse", &result.parse),
+        ("part 1", &result.part1),
+        ("part 2", &result.part2),
+    ] {
+        println!(
+            "  {:<8} {:>12} {:>12} {:>12}",
+            phase,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.p95)
+        );
+    }
+
+    println!();
+}
+
+fn print_summary(results: &[DayBench]) {
+    println!("Summary (median)");
+    println!(
+        "  {:<6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+
+    for result in results {
+        println!(
+            "  {:<6} {:>12} {:>12} {:>12} {:>12}",
+            format!("{:02}", result.day),
+            format!("{:.2?}", result.parse.median),
+            format!("{:.2?}", result.part1.median),
+            format!("{:.2?}", result.part2.median),
+            format!("{:.2?}", result.total_median())
+        );
+    }
+
+    let total: Duration = results.iter().map(DayBench::total_median).sum();
+    println!(
+        "  {:<6} {:>12} {:>12} {:>12} {:>12}",
+        "all",
+        "",
+        "",
+        "",
+        format!("{:.2?}", total)
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = (1..=100).rev().map(Duration::from_millis).collect();
+
+        assert_eq!(
+            Stats::from_samples(samples),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(50),
+                p95: Duration::from_millis(95),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_from_single_sample() {
+        let stats = Stats::from_samples(vec![Duration::from_micros(7)]);
+
+        assert_eq!(stats.min, Duration::from_micros(7));
+        assert_eq!(stats.median, Duration::from_micros(7));
+        assert_eq!(stats.p95, Duration::from_micros(7));
+    }
+}
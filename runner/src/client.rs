@@ -0,0 +1,204 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u16 = 2022;
+
+static USER_AGENT: &str = "github.com/tverghis/aoc2022_rust";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    /// The site didn't accept our session cookie, or we didn't send one.
+    NotLoggedIn,
+    /// The puzzle hasn't unlocked yet.
+    NotUnlocked,
+    UnexpectedResponse {
+        status: u16,
+        body: String,
+    },
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found; set AOC_SESSION, or write it to {} or .cookie",
+                config_session_path().map_or(String::from("~/.config/aoc/session"), |path| path
+                    .display()
+                    .to_string())
+            ),
+            ClientError::NotLoggedIn => write!(f, "not logged in; is the session cookie stale?"),
+            ClientError::NotUnlocked => write!(f, "the puzzle hasn't unlocked yet"),
+            ClientError::UnexpectedResponse { status, body } => {
+                let first_line = body.lines().next().unwrap_or_default();
+                write!(f, "unexpected response ({}): {}", status, first_line)
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// The value of the adventofcode.com `session` cookie.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Session(String);
+
+impl Session {
+    /// Accepts either the bare cookie value or `session=<value>`, as `setup.sh` used to.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value.strip_prefix("session=").unwrap_or(value);
+
+        (!value.is_empty()).then(|| Session(value.to_owned()))
+    }
+
+    /// Looks for the session in `$AOC_SESSION`, then the user's config directory, then a
+    /// `.cookie` file at the root of the workspace.
+    pub fn find() -> Result<Self, ClientError> {
+        let files = config_session_path()
+            .into_iter()
+            .chain([aoc::input::workspace_root().join(".cookie")])
+            .collect::<Vec<_>>();
+
+        Session::from_sources(env::var("AOC_SESSION").ok(), &files)
+    }
+
+    fn from_sources(env_value: Option<String>, files: &[PathBuf]) -> Result<Self, ClientError> {
+        env_value
+            .and_then(|value| Session::parse(&value))
+            .or_else(|| {
+                files
+                    .iter()
+                    .filter_map(|path| fs::read_to_string(path).ok())
+                    .find_map(|contents| Session::parse(&contents))
+            })
+            .ok_or(ClientError::MissingSession)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.0)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+fn config_session_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("aoc").join("session"))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Talks to adventofcode.com, or whatever stands in for it at `base_url`.
+pub struct Client {
+    base_url: String,
+    session: Session,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Session) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// A client for the real site, unless `$AOC_BASE_URL` points somewhere else.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+
+        Ok(Client::new(&base_url, Session::find()?))
+    }
+
+    pub fn get(&self, path: &str) -> Result<Response, ClientError> {
+        let request = self
+            .agent
+            .get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &self.session.cookie());
+
+        into_response(request.call())
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, ClientError> {
+        let request = self
+            .agent
+            .post(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &self.session.cookie());
+
+        into_response(request.send_form(form))
+    }
+}
+
+fn into_response(result: Result<ureq::Response, ureq::Error>) -> Result<Response, ClientError> {
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(ClientError::Transport(e.to_string())),
+    };
+
+    Ok(Response {
+        status: response.status(),
+        body: response.into_string()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_session_parse() {
+        assert_eq!(
+            Session::parse("abc123\n"),
+            Some(Session(String::from("abc123")))
+        );
+        assert_eq!(
+            Session::parse("session=abc123"),
+            Some(Session(String::from("abc123")))
+        );
+        assert_eq!(Session::parse("  \n"), None);
+    }
+
+    #[test]
+    fn test_session_from_sources() {
+        let dir = env::temp_dir().join(format!("aoc-session-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (missing, cookie) = (dir.join("missing"), dir.join(".cookie"));
+        fs::write(&cookie, "session=from-file\n").unwrap();
+
+        let from_env = Session::from_sources(
+            Some(String::from("from-env")),
+            std::slice::from_ref(&cookie),
+        );
+        let from_file = Session::from_sources(None, &[missing.clone(), cookie.clone()]);
+        let from_nothing = Session::from_sources(Some(String::new()), &[missing]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(from_env.unwrap(), Session(String::from("from-env")));
+        assert_eq!(from_file.unwrap(), Session(String::from("from-file")));
+        assert!(matches!(from_nothing, Err(ClientError::MissingSession)));
+    }
+}
@@ -0,0 +1,106 @@
+use aoc::differential::Implementation;
+use aoc::{Generator, Solver};
+
+pub static DAYS: &[&dyn Solver] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+];
+
+/// The days that can make up inputs of their own. New days don't need one.
+pub static GENERATORS: &[&dyn Generator] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+];
+
+/// Every implementation of each day's parts, with the generator `aoc diff` checks them with.
+pub static IMPLEMENTATIONS: &[(&dyn Generator, &[Implementation])] = &[
+    (&day01::Day01, day01::IMPLEMENTATIONS),
+    (&day02::Day02, day02::IMPLEMENTATIONS),
+    (&day03::Day03, day03::IMPLEMENTATIONS),
+    (&day04::Day04, day04::IMPLEMENTATIONS),
+    (&day05::Day05, day05::IMPLEMENTATIONS),
+    (&day06::Day06, day06::IMPLEMENTATIONS),
+];
+
+/// Renders a report on an input, or why it couldn't be read.
+pub type Report = fn(&str) -> Result<String, String>;
+
+/// The days with a report on their input beyond the answers, for `aoc stats`.
+pub static STATS: &[(u8, Report)] = &[(1, |input| {
+    let stats = day01::calorie_stats(input).map_err(|e| e.to_string())?;
+    Ok(stats.to_string())
+})];
+
+pub fn find(day: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().copied().find(|solver| solver.day() == day)
+}
+
+pub fn find_generator(day: u8) -> Option<&'static dyn Generator> {
+    GENERATORS
+        .iter()
+        .copied()
+        .find(|generator| generator.day() == day)
+}
+
+pub fn find_stats(day: u8) -> Option<Report> {
+    STATS
+        .iter()
+        .find(|&&(stats_day, _)| stats_day == day)
+        .map(|&(_, report)| report)
+}
+
+#[cfg(test)]
+mod test {
+    use aoc::Part;
+
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        for generator in GENERATORS {
+            let solver = find(generator.day()).unwrap();
+
+            for (seed, size) in [(0, 0), (1, 1), (2, 3), (3, 10), (4, 1000)] {
+                let generated = generator.generate(seed, size);
+                let run = solver.solve(&generated.input, &Part::ALL).unwrap();
+                let answers = run
+                    .parts
+                    .into_iter()
+                    .map(|part_run| part_run.answer)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    answers,
+                    [generated.answer1, generated.answer2],
+                    "day {}, seed {}, size {}",
+                    generator.day(),
+                    seed,
+                    size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        for generator in GENERATORS {
+            let generate = |seed| generator.generate(seed, 50);
+
+            assert_eq!(generate(1), generate(1), "day {}", generator.day());
+            assert_ne!(
+                generate(1).input,
+                generate(2).input,
+                "day {}",
+                generator.day()
+            );
+        }
+    }
+}
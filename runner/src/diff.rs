@@ -0,0 +1,51 @@
+use std::ops::Range;
+use std::panic;
+use std::process::ExitCode;
+
+use aoc::differential;
+
+use crate::days;
+
+/// Cross-checks every implementation of `day`'s parts, or of every day's, on inputs generated
+/// from `seeds`, and prints the first input each day's implementations disagree on.
+pub fn diff(day: Option<u8>, seeds: Range<u64>, size: usize) -> ExitCode {
+    let registered = days::IMPLEMENTATIONS
+        .iter()
+        .filter(|(generator, _)| day.is_none_or(|day| generator.day() == day))
+        .collect::<Vec<_>>();
+
+    if let (Some(day), true) = (day, registered.is_empty()) {
+        eprintln!("error: no implementations registered for day {}", day);
+        return ExitCode::FAILURE;
+    }
+
+    // Implementations that panic are expected while minimizing, and are reported as outcomes.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut all_agree = true;
+    for (generator, implementations) in registered {
+        let day = generator.day();
+
+        match differential::cross_check(*generator, implementations, seeds.clone(), size) {
+            Ok(()) => println!(
+                "Day {:02}: {} implementations agree on {} inputs",
+                day,
+                implementations.len(),
+                seeds.end - seeds.start
+            ),
+            Err(divergence) => {
+                println!("Day {:02}: {}", day, divergence);
+                all_agree = false;
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+
+    if all_agree {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
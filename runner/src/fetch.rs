@@ -0,0 +1,252 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::client::{Client, ClientError, Response, DEFAULT_YEAR};
+
+/// Puzzle inputs as downloaded, one file per year and day. Cached inputs are never overwritten.
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: PathBuf) -> Self {
+        InputCache { dir }
+    }
+
+    /// `.cache/inputs` at the root of the workspace.
+    pub fn default_dir() -> PathBuf {
+        aoc::input::workspace_root().join(".cache").join("inputs")
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(year, day)) {
+            Ok(input) => Ok(Some(input)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fails with [`io::ErrorKind::AlreadyExists`] if the input is already cached.
+    pub fn store(&self, year: u16, day: u8, input: &str) -> io::Result<()> {
+        let path = self.path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?
+            .write_all(input.as_bytes())
+    }
+}
+
+/// Returns the cached input, or downloads and caches it if there is none. The client is only
+/// created if we need to download, so a cached input doesn't need a session.
+pub fn cached_or_fetch(
+    cache: &InputCache,
+    year: u16,
+    day: u8,
+    client: impl FnOnce() -> Result<Client, ClientError>,
+) -> Result<(String, bool), ClientError> {
+    if let Some(input) = cache.get(year, day)? {
+        return Ok((input, false));
+    }
+
+    let input = check_input(client()?.get(&format!("/{}/day/{}/input", year, day))?)?;
+    cache.store(year, day, &input)?;
+
+    Ok((input, true))
+}
+
+/// Makes sure that `response` is a puzzle input and not one of the site's error pages.
+fn check_input(response: Response) -> Result<String, ClientError> {
+    let body = response.body;
+
+    if body.contains("Please log in") {
+        return Err(ClientError::NotLoggedIn);
+    }
+    if body.contains("before it unlocks") {
+        return Err(ClientError::NotUnlocked);
+    }
+    if response.status != 200 || body.trim().is_empty() || body.trim_start().starts_with('<') {
+        return Err(ClientError::UnexpectedResponse {
+            status: response.status,
+            body,
+        });
+    }
+
+    Ok(body)
+}
+
+pub fn fetch(day: u8, year: Option<u16>) -> ExitCode {
+    let year = year.unwrap_or(DEFAULT_YEAR);
+    let cache = InputCache::new(InputCache::default_dir());
+
+    let input = match cached_or_fetch(&cache, year, day, Client::from_env) {
+        Ok((input, fetched)) => {
+            let verb = if fetched { "Fetched" } else { "Already cached" };
+            println!(
+                "{} the input for {} day {} at {}",
+                verb,
+                year,
+                day,
+                cache.path(year, day).display()
+            );
+            input
+        }
+        Err(e) => {
+            eprintln!(
+                "error: could not fetch the input for {} day {}: {}",
+                year, day, e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // The day crates only hold inputs for the year this workspace solves.
+    if year == DEFAULT_YEAR {
+        install(day, &input);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Copies `input` to the day's default input path, unless there is already something there.
+fn install(day: u8, input: &str) {
+    let path = aoc::input::default_path(day);
+
+    // Days that haven't been scaffolded yet have nowhere to put it.
+    if !path.parent().is_some_and(Path::is_dir) {
+        return;
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing == input => {}
+        Ok(_) => eprintln!(
+            "warning: {} differs from the fetched input; leaving it alone",
+            path.display()
+        ),
+        Err(_) => match fs::write(&path, input) {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(e) => eprintln!("warning: could not write {}: {}", path.display(), e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+    use crate::client::Session;
+    use crate::mock_server::MockServer;
+
+    static SAMPLE_INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+    fn temp_cache(name: &str) -> InputCache {
+        InputCache::new(env::temp_dir().join(format!(
+            "aoc-fetch-test-{}-{}",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn response(status: u16, body: &str) -> Response {
+        Response {
+            status,
+            body: String::from(body),
+        }
+    }
+
+    #[test]
+    fn test_check_input() {
+        assert_eq!(
+            check_input(response(200, SAMPLE_INPUT)).unwrap(),
+            SAMPLE_INPUT
+        );
+        assert!(matches!(
+            check_input(response(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            )),
+            Err(ClientError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            check_input(response(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!"
+            )),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            check_input(response(200, "<!DOCTYPE html>\n<html>")),
+            Err(ClientError::UnexpectedResponse { status: 200, .. })
+        ));
+        assert!(matches!(
+            check_input(response(500, "Internal Server Error")),
+            Err(ClientError::UnexpectedResponse { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cache_refuses_to_overwrite() {
+        let cache = temp_cache("overwrite");
+
+        let first = cache.store(2022, 6, SAMPLE_INPUT);
+        let second = cache.store(2022, 6, "something else\n");
+        let cached = cache.get(2022, 6);
+        let missing = cache.get(2021, 6);
+        fs::remove_dir_all(&cache.dir).unwrap();
+
+        first.unwrap();
+        assert_eq!(second.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(cached.unwrap().as_deref(), Some(SAMPLE_INPUT));
+        assert_eq!(missing.unwrap(), None);
+    }
+
+    #[test]
+    fn test_cached_or_fetch() {
+        let server = MockServer::start(|_| (200, String::from(SAMPLE_INPUT)));
+        let cache = temp_cache("fetch");
+        let client = || Ok(Client::new(&server.url, Session::parse("abc").unwrap()));
+
+        let fetched = cached_or_fetch(&cache, 2022, 6, client);
+        let cached = cached_or_fetch(&cache, 2022, 6, client);
+        fs::remove_dir_all(&cache.dir).unwrap();
+
+        assert_eq!(fetched.unwrap(), (String::from(SAMPLE_INPUT), true));
+        assert_eq!(cached.unwrap(), (String::from(SAMPLE_INPUT), false));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/6/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+    }
+
+    #[test]
+    fn test_error_page_is_not_cached() {
+        let server = MockServer::start(|_| {
+            (
+                404,
+                String::from("Please don't repeatedly request this endpoint before it unlocks!"),
+            )
+        });
+        let cache = temp_cache("unlock");
+        let client = || Ok(Client::new(&server.url, Session::parse("abc").unwrap()));
+
+        let result = cached_or_fetch(&cache, 2022, 25, client);
+
+        assert!(matches!(result, Err(ClientError::NotUnlocked)));
+        assert!(!cache.path(2022, 25).exists());
+    }
+}
@@ -0,0 +1,24 @@
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use crate::days;
+
+/// Writes a generated input for `day` to stdout, and the answers it should have to stderr, so
+/// that the input can be redirected to a file on its own.
+pub fn generate(day: u8, seed: u64, size: usize) -> ExitCode {
+    let Some(generator) = days::find_generator(day) else {
+        eprintln!("error: no generator registered for day {}", day);
+        return ExitCode::FAILURE;
+    };
+
+    let generated = generator.generate(seed, size);
+
+    if let Err(e) = io::stdout().lock().write_all(generated.input.as_bytes()) {
+        eprintln!("error: could not write the input: {}", e);
+        return ExitCode::FAILURE;
+    }
+    eprintln!("Day {:02}, Part 1: {}", day, generated.answer1);
+    eprintln!("Day {:02}, Part 2: {}", day, generated.answer2);
+
+    ExitCode::SUCCESS
+}
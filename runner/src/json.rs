@@ -0,0 +1,320 @@
+//! Machine-readable output for `aoc run`. Every part of every day is one record with the same
+//! fields, so consumers never need to know which day they are looking at. A day that failed still
+//! gets its records, with a null answer and the reason in `error`.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc::{Answer, Part, PartRun, Run};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON array holding every record.
+    Json,
+    /// One JSON record per line.
+    Ndjson,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or ndjson",
+                s
+            )),
+        }
+    }
+}
+
+pub struct Record<'a> {
+    pub day: u8,
+    pub input_hash: u64,
+    pub outcome: Outcome<'a>,
+}
+
+/// How one part of a day went.
+pub enum Outcome<'a> {
+    Solved {
+        parse_time: Duration,
+        part_run: &'a PartRun,
+    },
+    Failed {
+        part: Part,
+        error: &'a str,
+    },
+}
+
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"day\":{},", self.day)?;
+
+        match self.outcome {
+            Outcome::Solved {
+                parse_time,
+                part_run,
+            } => {
+                let answer_type = match part_run.answer {
+                    Answer::Number(_) => "number",
+                    Answer::Text(_) => "text",
+                };
+
+                // Answers are always strings: numbers can be larger than a JSON reader's doubles
+                // hold.
+                write!(
+                    f,
+                    "\"part\":{},\"answer\":{},\"type\":\"{}\",\"input_hash\":\"{:016x}\",\"parse_ns\":{},\"part_ns\":{},\"error\":null}}",
+                    part_run.part,
+                    Escaped(&part_run.answer.to_string()),
+                    answer_type,
+                    self.input_hash,
+                    parse_time.as_nanos(),
+                    part_run.time.as_nanos()
+                )
+            }
+            Outcome::Failed { part, error } => write!(
+                f,
+                "\"part\":{},\"answer\":null,\"type\":null,\"input_hash\":\"{:016x}\",\"parse_ns\":null,\"part_ns\":null,\"error\":{}}}",
+                part,
+                self.input_hash,
+                Escaped(error)
+            ),
+        }
+    }
+}
+
+/// A day's records: one per part it ran, or when it failed, one per part it was asked for.
+pub fn records<'a>(
+    day: u8,
+    input_hash: u64,
+    result: &'a Result<Run, String>,
+    parts: &[Part],
+) -> Vec<Record<'a>> {
+    let record = |outcome| Record {
+        day,
+        input_hash,
+        outcome,
+    };
+
+    match result {
+        Ok(run) => run
+            .parts
+            .iter()
+            .map(|part_run| {
+                record(Outcome::Solved {
+                    parse_time: run.parse_time,
+                    part_run,
+                })
+            })
+            .collect(),
+        Err(error) => parts
+            .iter()
+            .map(|&part| record(Outcome::Failed { part, error }))
+            .collect(),
+    }
+}
+
+/// Writes a string as a quoted JSON string.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"")?;
+
+        for c in self.0.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                '\t' => write!(f, "\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+
+        write!(f, "\"")
+    }
+}
+
+/// Reads the field called `name` back out of one rendered record: a string's contents, or a
+/// number as written. Missing and `null` fields are `None`.
+pub fn field(record: &str, name: &str) -> Option<String> {
+    let key = format!("{}:", Escaped(name));
+    let value = &record[record.find(&key)? + key.len()..];
+
+    let Some(string) = value.strip_prefix('"') else {
+        let end = value.find([',', '}']).unwrap_or(value.len());
+        return Some(&value[..end])
+            .filter(|value| *value != "null")
+            .map(str::to_owned);
+    };
+
+    let mut contents = String::new();
+    let mut chars = string.chars();
+    loop {
+        match chars.next()? {
+            '"' => return Some(contents),
+            '\\' => contents.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'u' => {
+                    let code = chars.by_ref().take(4).collect::<String>();
+                    char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+                }
+                c => c,
+            }),
+            c => contents.push(c),
+        }
+    }
+}
+
+/// Renders `records` as `format`, which must not be [`Format::Text`].
+pub fn render(format: Format, records: &[Record]) -> String {
+    let lines = records.iter().map(Record::to_string).collect::<Vec<_>>();
+
+    match format {
+        Format::Text => unreachable!("text output is not JSON"),
+        Format::Json if lines.is_empty() => String::from("[]\n"),
+        Format::Json => format!("[\n  {}\n]\n", lines.join(",\n  ")),
+        Format::Ndjson => lines.iter().map(|line| format!("{}\n", line)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn part_run(part: Part, answer: Answer, micros: u64) -> PartRun {
+        PartRun {
+            part,
+            answer,
+            time: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let part_run = part_run(Part::Two, Answer::from(45000usize), 3);
+        let record = Record {
+            day: 1,
+            input_hash: 0xff,
+            outcome: Outcome::Solved {
+                parse_time: Duration::from_micros(12),
+                part_run: &part_run,
+            },
+        };
+
+        assert_eq!(
+            record.to_string(),
+            "{\"day\":1,\"part\":2,\"answer\":\"45000\",\"type\":\"number\",\"input_hash\":\"00000000000000ff\",\"parse_ns\":12000,\"part_ns\":3000,\"error\":null}"
+        );
+
+        let record = Record {
+            day: 3,
+            input_hash: 0xff,
+            outcome: Outcome::Failed {
+                part: Part::One,
+                error: "no item in \"ab\"",
+            },
+        };
+
+        assert_eq!(
+            record.to_string(),
+            "{\"day\":3,\"part\":1,\"answer\":null,\"type\":null,\"input_hash\":\"00000000000000ff\",\"parse_ns\":null,\"part_ns\":null,\"error\":\"no item in \\\"ab\\\"\"}"
+        );
+    }
+
+    #[test]
+    fn test_escaped() {
+        assert_eq!(Escaped("CMZ").to_string(), "\"CMZ\"");
+        assert_eq!(
+            Escaped("a\"b\\c\nd\u{1}").to_string(),
+            "\"a\\\"b\\\\c\\nd\\u0001\""
+        );
+    }
+
+    #[test]
+    fn test_field() {
+        let part_run = part_run(Part::One, Answer::from(String::from("a\"b\\c\nd\u{1}")), 3);
+        let record = Record {
+            day: 5,
+            input_hash: 0,
+            outcome: Outcome::Solved {
+                parse_time: Duration::ZERO,
+                part_run: &part_run,
+            },
+        }
+        .to_string();
+
+        assert_eq!(field(&record, "day").as_deref(), Some("5"));
+        assert_eq!(field(&record, "part_ns").as_deref(), Some("3000"));
+        assert_eq!(field(&record, "answer").as_deref(), Some("a\"b\\c\nd\u{1}"));
+        assert_eq!(field(&record, "type").as_deref(), Some("text"));
+        assert_eq!(field(&record, "error"), None);
+        assert_eq!(field("{\"answer\":null}", "answer"), None);
+        assert_eq!(field("{\"answer\":\"unterminated", "answer"), None);
+    }
+
+    #[test]
+    fn test_records() {
+        let run = Ok(Run {
+            parse_time: Duration::ZERO,
+            parts: vec![part_run(Part::Two, Answer::from(4usize), 1)],
+        });
+        let solved = records(4, 0, &run, &Part::ALL);
+
+        assert_eq!(solved.len(), 1);
+        assert_eq!(
+            field(&solved[0].to_string(), "answer").as_deref(),
+            Some("4")
+        );
+
+        let error = Err(String::from("the solver for day 4 failed"));
+        let failed = records(4, 0, &error, &Part::ALL)
+            .iter()
+            .map(Record::to_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(failed.len(), 2);
+        assert_eq!(field(&failed[1], "part").as_deref(), Some("2"));
+        assert!(failed
+            .iter()
+            .all(|record| field(record, "answer").is_none()));
+        assert!(failed.iter().all(|record| {
+            field(record, "error").as_deref() == Some("the solver for day 4 failed")
+        }));
+    }
+
+    #[test]
+    fn test_render() {
+        let run = Ok(Run {
+            parse_time: Duration::ZERO,
+            parts: vec![
+                part_run(Part::One, Answer::from(String::from("CMZ")), 1),
+                part_run(Part::Two, Answer::from(String::from("MCD")), 1),
+            ],
+        });
+        let records = records(5, 0, &run, &Part::ALL);
+
+        let json = render(Format::Json, &records);
+        let ndjson = render(Format::Ndjson, &records);
+
+        assert!(json.starts_with("[\n  {\"day\":5,\"part\":1,\"answer\":\"CMZ\",\"type\":\"text\""));
+        assert!(json.ends_with("\"part_ns\":1000,\"error\":null}\n]\n"));
+        assert_eq!(json.matches("},\n  {").count(), 1);
+        assert_eq!(ndjson.lines().count(), 2);
+        assert!(ndjson
+            .lines()
+            .all(|line| line.starts_with('{') && line.ends_with('}')));
+        assert_eq!(render(Format::Json, &[]), "[]\n");
+    }
+}
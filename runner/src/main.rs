@@ -0,0 +1,131 @@
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+
+use aoc::input::InputSource;
+use aoc::{Part, Run, Solver};
+use args::{Command, USAGE};
+use json::Format;
+
+mod answers;
+mod args;
+mod batch;
+mod bench;
+mod client;
+mod days;
+mod diff;
+mod fetch;
+mod generate;
+mod json;
+#[cfg(test)]
+mod mock_server;
+mod run_all;
+mod scaffold;
+mod stats;
+mod submissions;
+mod submit;
+mod verify;
+mod watch;
+
+fn main() -> ExitCode {
+    let command = match args::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => run(day, part, &input, format),
+        Command::RunAll { part, format } => run_all::run_all(part, format),
+        Command::RunBatch { day, part, dir } => batch::run_batch(day, part, &dir),
+        Command::Bench {
+            day,
+            iterations,
+            input,
+        } => bench::bench(day, iterations, &input),
+        Command::Verify { day, input, record } => verify::verify(day, &input, record),
+        Command::New { day } => scaffold::new_day(day),
+        Command::Fetch { day, year } => fetch::fetch(day, year),
+        Command::Submit { day, part, input } => submit::submit(day, part, &input),
+        Command::Generate { day, seed, size } => generate::generate(day, seed, size),
+        Command::Watch { day } => watch::watch(day),
+        Command::Diff { day, seeds, size } => diff::diff(day, seeds, size),
+        Command::Stats { day, input } => stats::stats(day, &input),
+    }
+}
+
+fn run(day: u8, part: Option<Part>, source: &InputSource, format: Format) -> ExitCode {
+    let Some(solver) = find_solver(day) else {
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = read_input(day, source) else {
+        return ExitCode::FAILURE;
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let result = solve(solver, &input, &parts);
+    if format != Format::Text {
+        let records = json::records(day, aoc::input::hash(&input), &result, &parts);
+        print!("{}", json::render(format, &records));
+    } else if let Ok(run) = &result {
+        for part_run in &run.parts {
+            println!(
+                "Day {:02}, Part {}: {}",
+                day, part_run.part, part_run.answer
+            );
+        }
+    }
+
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn find_solver(day: u8) -> Option<&'static dyn Solver> {
+    let solver = days::find(day);
+    if solver.is_none() {
+        eprintln!("error: no solver registered for day {}", day);
+    }
+
+    solver
+}
+
+fn read_input(day: u8, source: &InputSource) -> Option<String> {
+    match source.read(day) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!(
+                "error: could not read the input from {}: {}",
+                source.describe(day),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Runs `solver`, turning both parse errors and panics into an error message.
+fn solve(solver: &dyn Solver, input: &str, parts: &[Part]) -> Result<Run, String> {
+    let day = solver.day();
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.solve(input, parts))) {
+        Ok(Ok(run)) => Ok(run),
+        Ok(Err(e)) => Err(format!("could not parse the input for day {}: {}", day, e)),
+        Err(_) => Err(format!("the solver for day {} failed", day)),
+    }
+}
@@ -0,0 +1,240 @@
+use std::num::NonZeroUsize;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::input::InputSource;
+use aoc::{Part, Run, Solver};
+
+use crate::json::{self, Format};
+use crate::{days, solve};
+
+/// One day's run on its default input.
+struct DayRun {
+    day: u8,
+    input_hash: u64,
+    result: Result<Run, String>,
+}
+
+impl DayRun {
+    fn time(&self) -> Duration {
+        match &self.result {
+            Ok(run) => run.parse_time + run.parts.iter().map(|part_run| part_run.time).sum(),
+            Err(_) => Duration::ZERO,
+        }
+    }
+}
+
+/// Runs every registered day on its default input at once, and prints the answers in day order
+/// once they are all in.
+pub fn run_all(part: Option<Part>, format: Format) -> ExitCode {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let start = Instant::now();
+    let day_runs = parallel_map(days::DAYS, |&solver| run_day(solver, &parts));
+    let wall_time = start.elapsed();
+
+    if format == Format::Text {
+        print!("{}", summary(&day_runs, &parts, wall_time));
+    } else {
+        let records = day_runs
+            .iter()
+            .flat_map(|day_run| {
+                json::records(day_run.day, day_run.input_hash, &day_run.result, &parts)
+            })
+            .collect::<Vec<_>>();
+
+        print!("{}", json::render(format, &records));
+    }
+
+    let mut failed = false;
+    for day_run in &day_runs {
+        if let Err(e) = &day_run.result {
+            eprintln!("error: {}", e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_day(solver: &dyn Solver, parts: &[Part]) -> DayRun {
+    let day = solver.day();
+    let source = InputSource::Default;
+
+    match source.read(day) {
+        Ok(input) => DayRun {
+            day,
+            input_hash: aoc::input::hash(&input),
+            result: solve(solver, &input, parts),
+        },
+        Err(e) => DayRun {
+            day,
+            input_hash: 0,
+            result: Err(format!(
+                "could not read the input from {}: {}",
+                source.describe(day),
+                e
+            )),
+        },
+    }
+}
+
+/// Maps `items` on a pool of worker threads, one per core, that each take the next item nobody
+/// has started on yet. The results are in the order of `items`.
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(idx) else {
+                            return results;
+                        };
+                        results.push((idx, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("a worker thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|&(idx, _)| idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The table of every day's answers and times. The total is the time spent solving summed over
+/// the days, next to the wall time it actually took to run them all at once.
+fn summary(day_runs: &[DayRun], parts: &[Part], wall_time: Duration) -> String {
+    let mut table = format!("  {:<6}", "day");
+    for part in parts {
+        table.push_str(&format!(" {:<16}", format!("part {}", part)));
+    }
+    table.push_str(&format!(" {:>12}\n", "time"));
+
+    for day_run in day_runs {
+        table.push_str(&format!("  {:<6}", format!("{:02}", day_run.day)));
+
+        match &day_run.result {
+            Ok(run) => {
+                for part_run in &run.parts {
+                    table.push_str(&format!(" {:<16}", part_run.answer.to_string()));
+                }
+                table.push_str(&format!(" {:>12}\n", format!("{:.2?}", day_run.time())));
+            }
+            Err(_) => {
+                for _ in parts {
+                    table.push_str(&format!(" {:<16}", "failed"));
+                }
+                table.push_str(&format!(" {:>12}\n", "-"));
+            }
+        }
+    }
+
+    let total = day_runs.iter().map(DayRun::time).sum::<Duration>();
+    table.push_str(&format!(
+        "  {:<6}{} {:>12}\n  {:<6}{} {:>12}\n",
+        "total",
+        " ".repeat(17 * parts.len()),
+        format!("{:.2?}", total),
+        "wall",
+        " ".repeat(17 * parts.len()),
+        format!("{:.2?}", wall_time)
+    ));
+
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use aoc::{Answer, PartRun};
+
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        let threads = Mutex::new(HashSet::new());
+
+        let squares = parallel_map(&items, |&n| {
+            threads.lock().unwrap().insert(thread::current().id());
+            n * n
+        });
+
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert!(!threads
+            .into_inner()
+            .unwrap()
+            .contains(&thread::current().id()));
+        assert!(parallel_map(&[] as &[u64], |&n| n).is_empty());
+    }
+
+    #[test]
+    fn test_summary() {
+        let run = |answers: [u64; 2]| Run {
+            parse_time: Duration::from_millis(1),
+            parts: Part::ALL
+                .iter()
+                .zip(answers)
+                .map(|(&part, answer)| PartRun {
+                    part,
+                    answer: Answer::from(answer),
+                    time: Duration::from_millis(2),
+                })
+                .collect(),
+        };
+        let day_runs = [
+            DayRun {
+                day: 1,
+                input_hash: 0,
+                result: Ok(run([24000, 45000])),
+            },
+            DayRun {
+                day: 2,
+                input_hash: 0,
+                result: Err(String::from("could not parse the input for day 2")),
+            },
+            DayRun {
+                day: 4,
+                input_hash: 0,
+                result: Ok(run([2, 4])),
+            },
+        ];
+
+        let summary = summary(&day_runs, &Part::ALL, Duration::from_millis(6));
+        let lines = summary.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("  day    part 1           part 2"));
+        assert!(lines[1].starts_with("  01     24000            45000"));
+        assert!(lines[1].ends_with("5.00ms"));
+        assert!(lines[2].starts_with("  02     failed           failed"));
+        assert!(lines[3].starts_with("  04     2                4"));
+        assert!(lines[4].starts_with("  total") && lines[4].ends_with("10.00ms"));
+        assert!(lines[5].starts_with("  wall") && lines[5].ends_with("6.00ms"));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+}
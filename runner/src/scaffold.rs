@@ -0,0 +1,199 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+
+static CARGO_TOML_TEMPLATE: &str = include_str!("../templates/Cargo.toml.txt");
+static LIB_RS_TEMPLATE: &str = include_str!("../templates/lib.rs.txt");
+static MAIN_RS_TEMPLATE: &str = include_str!("../templates/main.rs.txt");
+static FUZZ_TARGET_TEMPLATE: &str = include_str!("../templates/fuzz_target.rs.txt");
+
+pub fn new_day(day: u8) -> ExitCode {
+    let root = aoc::input::workspace_root();
+
+    match scaffold(&root, day) {
+        Ok(()) => {
+            println!(
+                "Created day{:02}. Run `aoc fetch {}` or put your puzzle input in day{:02}/input.txt",
+                day, day, day
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: could not create day {}: {}", day, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Creates the `dayNN` crate under the workspace at `root` and registers it with the workspace,
+/// the runner and the fuzz targets.
+fn scaffold(root: &Path, day: u8) -> io::Result<()> {
+    let crate_name = format!("day{:02}", day);
+    let struct_name = format!("Day{:02}", day);
+    let render = |template: &str| {
+        template
+            .replace("{{crate}}", &crate_name)
+            .replace("{{struct}}", &struct_name)
+            .replace("{{day}}", &day.to_string())
+    };
+
+    // Register first, so that nothing is left half-created if one of the files has changed shape.
+    let workspace_toml = insert_sorted(
+        &fs::read_to_string(root.join("Cargo.toml"))?,
+        "members = [",
+        &format!("    \"{}\",", crate_name),
+    )?;
+    let runner_toml = insert_sorted(
+        &fs::read_to_string(root.join("runner/Cargo.toml"))?,
+        "[dependencies]",
+        &format!("{} = {{ path = \"../{}\" }}", crate_name, crate_name),
+    )?;
+    let days_rs = insert_sorted(
+        &fs::read_to_string(root.join("runner/src/days.rs"))?,
+        "pub static DAYS: &[&dyn Solver] = &[",
+        &format!("    &{}::{},", crate_name, struct_name),
+    )?;
+    let mut fuzz_toml = insert_sorted(
+        &fs::read_to_string(root.join("fuzz/Cargo.toml"))?,
+        "[dependencies]",
+        &format!("{} = {{ path = \"../{}\" }}", crate_name, crate_name),
+    )?;
+    fuzz_toml.push_str(&format!(
+        "\n[[bin]]\nname = \"{0}\"\npath = \"fuzz_targets/{0}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        crate_name
+    ));
+    let fuzz_target = root.join(format!("fuzz/fuzz_targets/{}.rs", crate_name));
+    if fuzz_target.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", fuzz_target.display()),
+        ));
+    }
+
+    let dir = root.join(&crate_name);
+    fs::create_dir(&dir)?;
+    fs::create_dir(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TOML_TEMPLATE))?;
+    fs::write(dir.join("src/lib.rs"), render(LIB_RS_TEMPLATE))?;
+    fs::write(dir.join("src/main.rs"), render(MAIN_RS_TEMPLATE))?;
+    fs::write(fuzz_target, render(FUZZ_TARGET_TEMPLATE))?;
+
+    fs::write(root.join("Cargo.toml"), workspace_toml)?;
+    fs::write(root.join("runner/Cargo.toml"), runner_toml)?;
+    fs::write(root.join("runner/src/days.rs"), days_rs)?;
+    fs::write(root.join("fuzz/Cargo.toml"), fuzz_toml)?;
+
+    Ok(())
+}
+
+/// Adds `entry` to the block of lines that follows the line `start`, keeping the block sorted.
+///
+/// The block ends at the first blank line, the first line starting with a bracket or the end of
+/// `contents`.
+fn insert_sorted(contents: &str, start: &str, entry: &str) -> io::Result<String> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let start_idx = lines
+        .iter()
+        .position(|line| line.trim() == start)
+        .ok_or_else(|| invalid(format!("could not find {:?}", start)))?;
+
+    let block_len = lines[start_idx + 1..]
+        .iter()
+        .take_while(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('[') && !line.starts_with(']')
+        })
+        .count();
+    let block = &lines[start_idx + 1..start_idx + 1 + block_len];
+
+    if block.contains(&entry) {
+        return Err(invalid(format!("{:?} is already registered", entry.trim())));
+    }
+
+    let insert_idx = start_idx + 1 + block.iter().take_while(|&&line| line < entry).count();
+    lines.insert(insert_idx, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let contents =
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"runner\",\n]\n";
+
+        assert_eq!(
+            insert_sorted(contents, "members = [", "    \"day02\",").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"runner\",\n]\n"
+        );
+        assert!(insert_sorted(contents, "members = [", "    \"day01\",").is_err());
+        assert!(insert_sorted(contents, "[dependencies]", "day01 = {}").is_err());
+    }
+
+    #[test]
+    fn test_insert_sorted_at_end_of_file() {
+        let contents =
+            "[dependencies]\naoc = { path = \"../aoc\" }\nday01 = { path = \"../day01\" }\n";
+
+        assert_eq!(
+            insert_sorted(
+                contents,
+                "[dependencies]",
+                "day02 = { path = \"../day02\" }"
+            )
+            .unwrap(),
+            contents.to_owned() + "day02 = { path = \"../day02\" }\n"
+        );
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("runner/src")).unwrap();
+        fs::create_dir_all(root.join("fuzz/fuzz_targets")).unwrap();
+        fs::write(root.join("Cargo.toml"), include_str!("../../Cargo.toml")).unwrap();
+        fs::write(
+            root.join("runner/Cargo.toml"),
+            include_str!("../Cargo.toml"),
+        )
+        .unwrap();
+        fs::write(root.join("runner/src/days.rs"), include_str!("days.rs")).unwrap();
+        fs::write(
+            root.join("fuzz/Cargo.toml"),
+            include_str!("../../fuzz/Cargo.toml"),
+        )
+        .unwrap();
+
+        let result = scaffold(&root, 25);
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        let (workspace_toml, runner_toml, days_rs, lib_rs) = (
+            read("Cargo.toml"),
+            read("runner/Cargo.toml"),
+            read("runner/src/days.rs"),
+            read("day25/src/lib.rs"),
+        );
+        let (fuzz_toml, fuzz_target) =
+            (read("fuzz/Cargo.toml"), read("fuzz/fuzz_targets/day25.rs"));
+        let again = scaffold(&root, 25);
+        fs::remove_dir_all(&root).unwrap();
+
+        result.unwrap();
+        assert!(workspace_toml.contains("    \"day25\",\n    \"runner\",\n"));
+        assert!(runner_toml.contains("day25 = { path = \"../day25\" }"));
+        assert!(days_rs.contains("    &day25::Day25,\n];"));
+        assert!(lib_rs.contains("impl Solution for Day25 {\n    const DAY: u8 = 25;"));
+        assert!(!lib_rs.contains("todo!"));
+        assert!(fuzz_toml.contains("day25 = { path = \"../day25\" }\nlibfuzzer-sys"));
+        assert!(fuzz_toml.ends_with("[[bin]]\nname = \"day25\"\npath = \"fuzz_targets/day25.rs\"\ntest = false\ndoc = false\nbench = false\n"));
+        assert!(fuzz_target.contains("use day25::Day25;"));
+        assert!(again.is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use std::process::ExitCode;
+
+use aoc::input::InputSource;
+
+use crate::{days, read_input};
+
+/// Prints what a day's input looks like beyond its answers, for the days that have a report.
+pub fn stats(day: u8, source: &InputSource) -> ExitCode {
+    let Some(report) = days::find_stats(day) else {
+        eprintln!("error: no statistics report registered for day {}", day);
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = read_input(day, source) else {
+        return ExitCode::FAILURE;
+    };
+
+    match report(&input) {
+        Ok(report) => {
+            print!("{}", report);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
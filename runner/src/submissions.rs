@@ -0,0 +1,276 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc::Part;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        };
+
+        write!(f, "{}", verdict)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            _ => Err(format!("unknown verdict {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Every answer `aoc submit` has sent, and until when the site wants us to hold off.
+///
+/// On disk this is one `<year> <day> <part> <verdict> <answer>` line per submission, plus a
+/// `wait-until <unix seconds>` line while a cooldown is known.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct SubmissionLog {
+    pub wait_until: Option<u64>,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Kept next to the cached inputs, since it is as personal as they are.
+    pub fn default_path() -> PathBuf {
+        aoc::input::workspace_root()
+            .join(".cache")
+            .join("submissions.txt")
+    }
+
+    /// Loads the log at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, self.to_string())
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Explains why submitting `answer` would be pointless, or too early, at time `now`.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), String> {
+        let number = answer.parse::<u64>().ok();
+
+        for submission in &self.submissions {
+            if (submission.year, submission.day, submission.part) != (year, day, part) {
+                continue;
+            }
+
+            let previous = &submission.answer;
+            let numbers = number.zip(previous.parse::<u64>().ok());
+
+            match submission.verdict {
+                Verdict::Right => {
+                    return Err(format!("this part was already solved with {}", previous))
+                }
+                _ if previous == answer => {
+                    return Err(format!(
+                        "{} was already submitted, and it was {}",
+                        answer, submission.verdict
+                    ))
+                }
+                Verdict::TooHigh
+                    if numbers.is_some_and(|(number, previous)| number >= previous) =>
+                {
+                    return Err(format!(
+                        "{} is at least {}, which was too high",
+                        answer, previous
+                    ))
+                }
+                Verdict::TooLow if numbers.is_some_and(|(number, previous)| number <= previous) => {
+                    return Err(format!(
+                        "{} is at most {}, which was too low",
+                        answer, previous
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        match self.wait_until {
+            Some(wait_until) if now < wait_until => Err(format!(
+                "the site asked us to wait another {}s before submitting again",
+                wait_until - now
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for SubmissionLog {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut log = SubmissionLog::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("line {}: invalid submission entry {:?}", idx + 1, line);
+
+            if let Some(wait_until) = line.strip_prefix("wait-until ") {
+                log.wait_until = Some(wait_until.parse().map_err(|_| invalid())?);
+                continue;
+            }
+
+            let [year, day, part, verdict, answer]: [&str; 5] = line
+                .splitn(5, ' ')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+
+            log.record(Submission {
+                year: year.parse().map_err(|_| invalid())?,
+                day: day.parse().map_err(|_| invalid())?,
+                part: part.parse().map_err(|_| invalid())?,
+                verdict: verdict.parse().map_err(|_| invalid())?,
+                answer: answer.to_owned(),
+            });
+        }
+
+        Ok(log)
+    }
+}
+
+impl fmt::Display for SubmissionLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Answers sent by `aoc submit`.")?;
+        writeln!(f, "# year day part verdict answer")?;
+
+        if let Some(wait_until) = self.wait_until {
+            writeln!(f, "wait-until {}", wait_until)?;
+        }
+
+        for submission in &self.submissions {
+            writeln!(
+                f,
+                "{} {:02} {} {} {}",
+                submission.year,
+                submission.day,
+                submission.part,
+                submission.verdict,
+                submission.answer
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn submission(part: Part, verdict: Verdict, answer: &str) -> Submission {
+        Submission {
+            year: 2022,
+            day: 1,
+            part,
+            verdict,
+            answer: String::from(answer),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut log = SubmissionLog {
+            wait_until: Some(1_670_000_000),
+            ..SubmissionLog::default()
+        };
+        log.record(submission(Part::One, Verdict::TooHigh, "72719"));
+        log.record(submission(Part::Two, Verdict::Wrong, "MCD"));
+
+        let text = log.to_string();
+
+        assert!(text
+            .ends_with("wait-until 1670000000\n2022 01 1 too-high 72719\n2022 01 2 wrong MCD\n"));
+        assert_eq!(text.parse(), Ok(log));
+    }
+
+    #[test]
+    fn test_check() {
+        let mut log = SubmissionLog::default();
+        log.record(submission(Part::One, Verdict::TooHigh, "100"));
+        log.record(submission(Part::One, Verdict::TooLow, "10"));
+        log.record(submission(Part::One, Verdict::Wrong, "50"));
+        log.record(submission(Part::Two, Verdict::Right, "7"));
+
+        assert_eq!(log.check(2022, 1, Part::One, "42", 0), Ok(()));
+        assert!(log.check(2022, 1, Part::One, "50", 0).is_err());
+        assert!(log.check(2022, 1, Part::One, "100", 0).is_err());
+        assert!(log.check(2022, 1, Part::One, "150", 0).is_err());
+        assert!(log.check(2022, 1, Part::One, "5", 0).is_err());
+        assert!(log.check(2022, 1, Part::Two, "8", 0).is_err());
+        assert_eq!(log.check(2021, 1, Part::One, "150", 0), Ok(()));
+    }
+
+    #[test]
+    fn test_check_cooldown() {
+        let log = SubmissionLog {
+            wait_until: Some(1000),
+            ..SubmissionLog::default()
+        };
+
+        assert_eq!(
+            log.check(2022, 1, Part::One, "42", 940),
+            Err(String::from(
+                "the site asked us to wait another 60s before submitting again"
+            ))
+        );
+        assert_eq!(log.check(2022, 1, Part::One, "42", 1000), Ok(()));
+    }
+}
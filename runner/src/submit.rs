@@ -0,0 +1,354 @@
+use std::fmt;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc::input::InputSource;
+use aoc::Part;
+
+use crate::answers::AnswerStore;
+use crate::client::{Client, ClientError, DEFAULT_YEAR};
+use crate::submissions::{Submission, SubmissionLog, Verdict};
+use crate::{find_solver, read_input, solve};
+
+/// How the site replied to an answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Reply {
+    Verdict(Verdict),
+    /// The part is already solved, or its first part isn't.
+    WrongLevel,
+    /// We submitted too soon after the last answer.
+    TooSoon,
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The submission log already tells us what the site would say.
+    Refused(String),
+    Client(ClientError),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(reason) => write!(f, "not submitting: {}", reason),
+            SubmitError::Client(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<ClientError> for SubmitError {
+    fn from(e: ClientError) -> Self {
+        SubmitError::Client(e)
+    }
+}
+
+/// Finds out what the answer page says, and how long it wants us to wait before the next
+/// submission.
+fn parse_reply(body: &str) -> Option<(Reply, Option<Duration>)> {
+    let text = body.to_lowercase();
+
+    let reply = if text.contains("that's the right answer") {
+        Reply::Verdict(Verdict::Right)
+    } else if text.contains("your answer is too high") {
+        Reply::Verdict(Verdict::TooHigh)
+    } else if text.contains("your answer is too low") {
+        Reply::Verdict(Verdict::TooLow)
+    } else if text.contains("that's not the right answer") {
+        Reply::Verdict(Verdict::Wrong)
+    } else if text.contains("you don't seem to be solving the right level") {
+        Reply::WrongLevel
+    } else if text.contains("you gave an answer too recently") {
+        Reply::TooSoon
+    } else {
+        return None;
+    };
+
+    Some((reply, parse_wait(&text)))
+}
+
+/// Understands "you have 1m 5s left to wait" and "please wait one minute" / "please wait 5
+/// minutes before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let (_, left) = before.rsplit_once("you have ")?;
+
+        return left
+            .split_whitespace()
+            .try_fold(Duration::ZERO, |total, amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let seconds = match unit {
+                    "h" => 3600,
+                    "m" => 60,
+                    "s" => 1,
+                    _ => return None,
+                };
+
+                Some(total + Duration::from_secs(number.parse::<u64>().ok()? * seconds))
+            });
+    }
+
+    let (_, rest) = text.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+/// Submits `answer` unless `log` says it is pointless or too early, and records the outcome in
+/// `log`.
+pub fn submit_answer(
+    client: &Client,
+    log: &mut SubmissionLog,
+    (year, day, part): (u16, u8, Part),
+    answer: &str,
+    now: u64,
+) -> Result<Reply, SubmitError> {
+    log.check(year, day, part, answer, now)
+        .map_err(SubmitError::Refused)?;
+
+    let level = part.to_string();
+    let response = client.post_form(
+        &format!("/{}/day/{}/answer", year, day),
+        &[("level", &level), ("answer", answer)],
+    )?;
+
+    if response.body.contains("Please log in") {
+        return Err(ClientError::NotLoggedIn.into());
+    }
+    let Some((reply, wait)) = parse_reply(&response.body) else {
+        return Err(ClientError::UnexpectedResponse {
+            status: response.status,
+            body: response.body,
+        }
+        .into());
+    };
+
+    if let Some(wait) = wait {
+        log.wait_until = Some(now + wait.as_secs());
+    }
+    if let Reply::Verdict(verdict) = reply {
+        log.record(Submission {
+            year,
+            day,
+            part,
+            verdict,
+            answer: answer.to_owned(),
+        });
+    }
+
+    Ok(reply)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs())
+}
+
+pub fn submit(day: u8, part: Part, source: &InputSource) -> ExitCode {
+    let Some(solver) = find_solver(day) else {
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = read_input(day, source) else {
+        return ExitCode::FAILURE;
+    };
+    let answer = match solve(solver, &input, &[part]) {
+        Ok(run) => run.parts[0].answer.to_string(),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let log_path = SubmissionLog::default_path();
+    let mut log = match SubmissionLog::load(&log_path) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("error: could not load {}: {}", log_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = Client::from_env()
+        .map_err(SubmitError::from)
+        .and_then(|client| {
+            submit_answer(
+                &client,
+                &mut log,
+                (DEFAULT_YEAR, day, part),
+                &answer,
+                unix_now(),
+            )
+        });
+
+    // Save even after an error, since the site may have asked us to wait.
+    if let Err(e) = log.save(&log_path) {
+        eprintln!("error: could not save {}: {}", log_path.display(), e);
+    }
+
+    let reply = match result {
+        Ok(reply) => reply,
+        Err(e) => {
+            eprintln!("error: day {} part {}: {}", day, part, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let message = match reply {
+        Reply::Verdict(Verdict::Right) => "right",
+        Reply::Verdict(Verdict::Wrong) => "wrong",
+        Reply::Verdict(Verdict::TooHigh) => "wrong, too high",
+        Reply::Verdict(Verdict::TooLow) => "wrong, too low",
+        Reply::WrongLevel => "not accepted; is this part already solved, or the one before not?",
+        Reply::TooSoon => "not accepted; the site says to wait before submitting again",
+    };
+    println!("Day {:02}, Part {}: {} is {}", day, part, answer, message);
+
+    if reply != Reply::Verdict(Verdict::Right) {
+        return ExitCode::FAILURE;
+    }
+
+    record_accepted(day, part, &input, answer);
+    ExitCode::SUCCESS
+}
+
+/// Adds a right answer to the answers file, so that `aoc verify` checks it from now on.
+fn record_accepted(day: u8, part: Part, input: &str, answer: String) {
+    let path = AnswerStore::default_path();
+    let result = AnswerStore::load(&path).and_then(|mut store| {
+        store.insert(day, part, aoc::input::hash(input), answer);
+        store.save(&path)
+    });
+
+    if let Err(e) = result {
+        eprintln!(
+            "error: could not record the answer in {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::Session;
+    use crate::mock_server::MockServer;
+
+    static RIGHT: &str = "<main><article><p>That's the right answer!  You are one gold star \
+        closer to collecting enough star fruit.</p></article></main>";
+    static TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+        high.  If you're stuck, make sure you're using the full input data.  Please wait one \
+        minute before trying again.</p></article></main>";
+    static WRONG: &str = "<main><article><p>That's not the right answer.  Because you have \
+        guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying \
+        again.</p></article></main>";
+    static TOO_SOON: &str = "<main><article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 1m 5s left to wait.</p>\
+        </article></main>";
+    static WRONG_LEVEL: &str = "<main><article><p>You don't seem to be solving the right level.  \
+        Did you already complete it?</p></article></main>";
+
+    fn client(server: &MockServer) -> Client {
+        Client::new(&server.url, Session::parse("abc").unwrap())
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(
+            parse_reply(RIGHT),
+            Some((Reply::Verdict(Verdict::Right), None))
+        );
+        assert_eq!(
+            parse_reply(TOO_HIGH),
+            Some((
+                Reply::Verdict(Verdict::TooHigh),
+                Some(Duration::from_secs(60))
+            ))
+        );
+        assert_eq!(
+            parse_reply(WRONG),
+            Some((
+                Reply::Verdict(Verdict::Wrong),
+                Some(Duration::from_secs(300))
+            ))
+        );
+        assert_eq!(
+            parse_reply(TOO_SOON),
+            Some((Reply::TooSoon, Some(Duration::from_secs(65))))
+        );
+        assert_eq!(parse_reply(WRONG_LEVEL), Some((Reply::WrongLevel, None)));
+        assert_eq!(parse_reply("<html>Something else</html>"), None);
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let server = MockServer::start(|_| (200, String::from(RIGHT)));
+        let mut log = SubmissionLog::default();
+
+        let reply = submit_answer(&client(&server), &mut log, (2022, 5, Part::Two), "MCD", 0);
+
+        assert_eq!(reply.unwrap(), Reply::Verdict(Verdict::Right));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/5/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+        assert_eq!(requests[0].body, "level=2&answer=MCD");
+        assert!(log.check(2022, 5, Part::Two, "MCD", 0).is_err());
+    }
+
+    #[test]
+    fn test_wrong_answer_is_not_resubmitted() {
+        let server = MockServer::start(|_| (200, String::from(TOO_HIGH)));
+        let mut log = SubmissionLog::default();
+        let client = client(&server);
+
+        let first = submit_answer(&client, &mut log, (2022, 1, Part::One), "72719", 1000);
+        let too_soon = submit_answer(&client, &mut log, (2022, 1, Part::One), "72000", 1030);
+        let same = submit_answer(&client, &mut log, (2022, 1, Part::One), "72719", 2000);
+        let higher = submit_answer(&client, &mut log, (2022, 1, Part::One), "80000", 2000);
+
+        assert_eq!(first.unwrap(), Reply::Verdict(Verdict::TooHigh));
+        assert_eq!(log.wait_until, Some(1060));
+        assert!(matches!(too_soon, Err(SubmitError::Refused(_))));
+        assert!(matches!(same, Err(SubmitError::Refused(_))));
+        assert!(matches!(higher, Err(SubmitError::Refused(_))));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_too_soon_sets_the_cooldown() {
+        let server = MockServer::start(|_| (200, String::from(TOO_SOON)));
+        let mut log = SubmissionLog::default();
+
+        let reply = submit_answer(&client(&server), &mut log, (2022, 6, Part::One), "7", 500);
+
+        assert_eq!(reply.unwrap(), Reply::TooSoon);
+        assert_eq!(log.wait_until, Some(565));
+        assert_eq!(log.check(2022, 6, Part::One, "7", 565), Ok(()));
+    }
+
+    #[test]
+    fn test_unexpected_reply() {
+        let server = MockServer::start(|_| (500, String::from("Internal Server Error")));
+        let mut log = SubmissionLog::default();
+
+        let reply = submit_answer(&client(&server), &mut log, (2022, 6, Part::One), "7", 0);
+
+        assert!(matches!(
+            reply,
+            Err(SubmitError::Client(ClientError::UnexpectedResponse {
+                status: 500,
+                ..
+            }))
+        ));
+        assert_eq!(log, SubmissionLog::default());
+    }
+}
@@ -0,0 +1,131 @@
+use std::process::ExitCode;
+
+use aoc::input::InputSource;
+use aoc::{Answer, Part};
+
+use crate::answers::AnswerStore;
+use crate::{days, find_solver, read_input, solve};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Check {
+    Unchanged,
+    Changed { expected: String },
+    Unrecorded,
+}
+
+fn check(expected: Option<&str>, actual: &Answer) -> Check {
+    match expected {
+        None => Check::Unrecorded,
+        Some(expected) if expected == actual.to_string() => Check::Unchanged,
+        Some(expected) => Check::Changed {
+            expected: expected.to_owned(),
+        },
+    }
+}
+
+/// Re-runs the solvers and compares their answers with the accepted ones in the answers file.
+///
+/// With `record`, answers for inputs that have none yet are accepted as they are. Answers that
+/// changed are never overwritten.
+pub fn verify(day: Option<u8>, source: &InputSource, record: bool) -> ExitCode {
+    let path = AnswerStore::default_path();
+    let mut store = match AnswerStore::load(&path) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("error: could not load {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let solvers = match day {
+        Some(day) => match find_solver(day) {
+            Some(solver) => vec![solver],
+            None => return ExitCode::FAILURE,
+        },
+        None => days::DAYS.to_vec(),
+    };
+
+    let (mut unchanged, mut changed, mut unrecorded, mut recorded, mut failed) = (0, 0, 0, 0, 0);
+
+    for solver in solvers {
+        let day = solver.day();
+        let Some(input) = read_input(day, source) else {
+            failed += 1;
+            continue;
+        };
+        let input_hash = aoc::input::hash(&input);
+
+        let run = match solve(solver, &input, &Part::ALL) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for part_run in run.parts {
+            let part = part_run.part;
+            let answer = part_run.answer;
+            let status = match check(store.get(day, part, input_hash), &answer) {
+                Check::Unchanged => {
+                    unchanged += 1;
+                    String::from("ok")
+                }
+                Check::Changed { expected } => {
+                    changed += 1;
+                    format!("CHANGED, expected {}", expected)
+                }
+                Check::Unrecorded if record => {
+                    recorded += 1;
+                    store.insert(day, part, input_hash, answer.to_string());
+                    String::from("recorded")
+                }
+                Check::Unrecorded => {
+                    unrecorded += 1;
+                    String::from("no accepted answer for this input")
+                }
+            };
+
+            println!("Day {:02}, Part {}: {} ({})", day, part, answer, status);
+        }
+    }
+
+    if recorded > 0 {
+        if let Err(e) = store.save(&path) {
+            eprintln!("error: could not save {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!(
+        "\n{} unchanged, {} changed, {} recorded, {} without an accepted answer, {} failed",
+        unchanged, changed, recorded, unrecorded, failed
+    );
+
+    if changed > 0 || failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            check(Some("24000"), &Answer::Number(24000)),
+            Check::Unchanged
+        );
+        assert_eq!(
+            check(Some("CMZ"), &Answer::Text(String::from("MCD"))),
+            Check::Changed {
+                expected: String::from("CMZ")
+            }
+        );
+        assert_eq!(check(None, &Answer::Number(1)), Check::Unrecorded);
+    }
+}
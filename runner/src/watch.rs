@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc::Part;
+
+use crate::{find_solver, json};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When each watched file was last modified.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// A day's answers, as `aoc run` printed them.
+type Answers = BTreeMap<Part, String>;
+
+/// Re-runs `day`'s tests and its solver on the real input every time its sources or its input
+/// change, and shows how the answers compare with the last run. Only stops when interrupted.
+///
+/// Both go through cargo, since the solver compiled into this binary is the one from before the
+/// change. The solver runs as `aoc run --format ndjson`, so that the answers come from the same
+/// records other tools read rather than from scraping text meant for people.
+pub fn watch(day: u8) -> ExitCode {
+    if find_solver(day).is_none() {
+        return ExitCode::FAILURE;
+    }
+
+    let root = aoc::input::workspace_root();
+    let paths = watched_paths(&root, day);
+    let mut seen = snapshot(&paths);
+    let mut last_answers = None;
+
+    loop {
+        if let Some(answers) = run_once(&root, day, last_answers.as_ref()) {
+            last_answers = Some(answers);
+        }
+
+        println!("\nWatching day {:02} for changes...", day);
+        seen = wait_for_change(&paths, seen);
+    }
+}
+
+/// Everything a day's answers depend on: its own crate, its input and the shared library.
+fn watched_paths(root: &Path, day: u8) -> Vec<PathBuf> {
+    let dir = root.join(format!("day{:02}", day));
+
+    vec![
+        dir.join("src"),
+        dir.join("Cargo.toml"),
+        dir.join("input.txt"),
+        root.join("aoc/src"),
+    ]
+}
+
+/// Looks at every file under `paths`. Paths that don't exist are left out, so that creating them
+/// counts as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path, modified);
+        }
+    }
+
+    snapshot
+}
+
+/// Polls until the files under `paths` differ from `seen`, then until they stop changing, since
+/// editors often save in several steps.
+fn wait_for_change(paths: &[PathBuf], seen: Snapshot) -> Snapshot {
+    let mut current = seen.clone();
+
+    while current == seen {
+        thread::sleep(POLL_INTERVAL);
+        current = snapshot(paths);
+    }
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let settled = snapshot(paths);
+        if settled == current {
+            return settled;
+        }
+        current = settled;
+    }
+}
+
+/// Runs the tests, then the solver. Returns the answers when the solver succeeded.
+fn run_once(root: &Path, day: u8, last_answers: Option<&Answers>) -> Option<Answers> {
+    let package = format!("day{:02}", day);
+
+    println!("Testing {}...", package);
+    match cargo(root).args(["test", "-q", "-p", &package]).status() {
+        Ok(status) if status.success() => println!("Tests passed"),
+        Ok(_) => println!("Tests FAILED"),
+        Err(e) => {
+            eprintln!("error: could not run cargo: {}", e);
+            return None;
+        }
+    }
+
+    println!("Solving {}...", package);
+    let output = match cargo(root)
+        .args(["run", "-q", "-p", "runner", "--", "run", &day.to_string()])
+        .args(["--format", "ndjson"])
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("error: could not run cargo: {}", e);
+            return None;
+        }
+    };
+    if !output.status.success() {
+        println!("The solver FAILED");
+        return None;
+    }
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    for line in compare_answers(last_answers, &answers) {
+        println!("{}", line);
+    }
+
+    Some(answers)
+}
+
+fn cargo(root: &Path) -> Command {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command.current_dir(root);
+
+    command
+}
+
+/// Picks each part's answer out of `aoc run --format ndjson` records.
+fn parse_answers(output: &str) -> Answers {
+    output
+        .lines()
+        .filter_map(|record| {
+            let part = json::field(record, "part")?.parse().ok()?;
+
+            Some((part, json::field(record, "answer")?))
+        })
+        .collect()
+}
+
+fn compare_answers(last_answers: Option<&Answers>, answers: &Answers) -> Vec<String> {
+    answers
+        .iter()
+        .map(|(part, answer)| {
+            let last = last_answers.and_then(|last_answers| last_answers.get(part));
+
+            match last {
+                None => format!("Part {}: {}", part, answer),
+                Some(last) if last == answer => format!("Part {}: {} (unchanged)", part, answer),
+                Some(last) => format!("Part {}: {} (CHANGED, was {})", part, answer, last),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc-watch-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let paths = [dir.join("src"), dir.join("input.txt")];
+
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+
+        fs::write(dir.join("input.txt"), "1000\n").unwrap();
+        let added = snapshot(&paths);
+        assert_eq!(added.len(), 2);
+
+        let file = File::options()
+            .write(true)
+            .open(dir.join("input.txt"))
+            .unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        let modified = snapshot(&paths);
+        assert_ne!(modified, added);
+        assert_eq!(modified.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(snapshot(&paths).is_empty());
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(concat!(
+            "{\"day\":5,\"part\":1,\"answer\":\"CMZ\",\"type\":\"text\"}\n",
+            "{\"day\":5,\"part\":2,\"answer\":\"MCD\",\"type\":\"text\"}\n",
+        ));
+
+        assert_eq!(
+            answers,
+            Answers::from([
+                (Part::One, String::from("CMZ")),
+                (Part::Two, String::from("MCD"))
+            ])
+        );
+        assert!(parse_answers("{\"day\":5,\"part\":3,\"answer\":\"7\"}\n").is_empty());
+        assert!(parse_answers("Part 1: CMZ\n").is_empty());
+    }
+
+    #[test]
+    fn test_compare_answers() {
+        let last = Answers::from([
+            (Part::One, String::from("24000")),
+            (Part::Two, String::from("45000")),
+        ]);
+        let answers = Answers::from([
+            (Part::One, String::from("24000")),
+            (Part::Two, String::from("45001")),
+        ]);
+
+        assert_eq!(
+            compare_answers(Some(&last), &answers),
+            [
+                "Part 1: 24000 (unchanged)",
+                "Part 2: 45001 (CHANGED, was 45000)"
+            ]
+        );
+        assert_eq!(
+            compare_answers(None, &answers),
+            ["Part 1: 24000", "Part 2: 45001"]
+        );
+    }
+}